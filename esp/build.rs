@@ -1,4 +1,8 @@
 fn main() {
+    // Host builds (`--no-default-features`, e.g. the tests) link as usual
+    if std::env::var_os("CARGO_FEATURE_ESP").is_none() {
+        return;
+    }
     linker_be_nice();
     // make sure linkall.x is the last linker script (otherwise might cause problems with flip-link)
     println!("cargo:rustc-link-arg=-Tlinkall.x");
@@ -11,17 +11,22 @@ use esp_hal::gpio::{Level, Output, OutputConfig};
 use esp_hal::main;
 use esp_hal::time::{Duration, Instant};
 use esp_hal::uart::{Config, Uart};
+use esp_hal::Blocking;
 use rtt_target::rprintln;
 use heapless::String;
-use core::fmt::Write;
-use kalesp::msg::{
-    Command, send_help_message, send_info_message, 
-    send_reset_message, send_unknown_command_message,
-    send_zeros_message, send_entropy_message, send_mine_start_message,
-    send_mine_result_message, send_mine_error_message, send_hash_message,
-    send_current_hash_message
-};
-use kalesp::mining::{MiningState, MinerFactory};
+use kalesp::msg::{Command, Event, ErrorSource, HashAlgorithm, OutputMode, Request, BAUD_RATE, MAX_LINE_LEN};
+use kalesp::hex::Hex;
+use kalesp::strkey::AccountId;
+use kalesp::frame::FrameReader;
+use kalesp::mining::{MiningState, MinerFactory, MinerType, MiningError, MiningJob, MiningOutcome, StepOutcome, ZeroChecker, digest, benchmark_algorithm};
+use kalesp::msg::{MiningBudget, ParseError};
+use kalesp::selftest;
+
+/// Nonces hashed by `estimate` when no job is running
+const ESTIMATE_CALIBRATION_NONCES: u32 = 4096;
+
+/// Longest a mining pass may hash before the console is read again
+const MINING_SLICE_MS: u32 = 4;
 
 #[panic_handler]
 fn panic(_: &core::panic::PanicInfo) -> ! {
@@ -31,6 +36,59 @@ fn panic(_: &core::panic::PanicInfo) -> ! {
 // This creates a default app-descriptor required by the esp-idf bootloader.
 esp_bootloader_esp_idf::esp_app_desc!();
 
+/// Where a reply goes - the protocol and the id of the request it answers
+#[derive(Debug, Clone, Copy)]
+struct Reply {
+    mode: OutputMode,
+    /// `#<id>` of a command line, or the seq of a frame
+    id: Option<u32>,
+}
+
+impl Reply {
+    const TEXT: Reply = Reply { mode: OutputMode::Text, id: None };
+}
+
+/// Mining configuration, the miner and the console protocol
+struct Session {
+    mining_state: MiningState,
+    miner: MinerType,
+    last_progress: Instant,
+    mode: OutputMode,
+    /// Id of the request that started the job, echoed by its events
+    job_id: Option<u32>,
+}
+
+impl Session {
+    /// Reply target for events of the running job
+    fn job_reply(&self) -> Reply {
+        Reply { mode: self.mode, id: self.job_id }
+    }
+}
+
+/// Receive side of the console - the line or frame arriving
+struct Console {
+    buffer: String<MAX_LINE_LEN>,
+    reader: FrameReader,
+    /// Set when `mode binary` ended with CR - its LF is still on the way
+    pending_lf: bool,
+    /// Set when the line outgrew the buffer - the rest of it is dropped
+    overflow: bool,
+}
+
+impl Console {
+    fn new() -> Self {
+        Self { buffer: String::new(), reader: FrameReader::new(), pending_lf: false, overflow: false }
+    }
+
+    /// Drops whatever was half received
+    fn clear(&mut self) {
+        self.buffer.clear();
+        self.reader.clear();
+        self.pending_lf = false;
+        self.overflow = false;
+    }
+}
+
 #[main]
 fn main() -> ! {
     rtt_target::rtt_init_print!();
@@ -42,20 +100,29 @@ fn main() -> ! {
     let mut led = Output::new(peripherals.GPIO2, Level::Low, OutputConfig::default());
 
     // Configure UART0 (uses ESP32 default pins: TX=GPIO1, RX=GPIO3)
-    let uart_config = Config::default();
+    let uart_config = Config::default().with_baudrate(BAUD_RATE);
     let mut uart = Uart::new(peripherals.UART0, uart_config).unwrap();
 
     rprintln!("Iniciando comunicação serial simples!");
-    write!(uart, "ESP32 Serial Comunicação iniciada!\r\n").ok();
-    write!(uart, "Digite 'help' para ver comandos disponíveis\r\n").ok();
+    emit(&mut uart, Reply::TEXT, &Event::Ready);
+
+    // Check the hashers on this chip before accepting jobs
+    let summary = run_selftest(&mut uart, Reply::TEXT);
+    rprintln!("Selftest: {}/{} casos OK", summary.passed, summary.total);
     
 
     let mut last_led_toggle = Instant::now();
-    let mut buffer: String<128> = String::new();
+    let mut console = Console::new();
     
     // Mining state
-    let mut mining_state = MiningState::new();
-    let mut current_miner = MinerFactory::create_miner_for_algorithm(mining_state.get_hash_algorithm());
+    let mining_state = MiningState::new();
+    let mut session = Session {
+        mining_state,
+        miner: MinerFactory::create_miner_for_algorithm(mining_state.get_hash_algorithm()),
+        last_progress: Instant::now(),
+        mode: OutputMode::Text,
+        job_id: None,
+    };
 
     loop {
         // Blink LED every 500ms to indicate it's working
@@ -64,93 +131,373 @@ fn main() -> ! {
             last_led_toggle = Instant::now();
         }
 
-        // Ler dados recebidos via UART
-        let mut temp_buffer = [0u8; 32];
-        if let Ok(bytes_read) = uart.read(&mut temp_buffer) {
-            if bytes_read > 0 {
-                for &byte in &temp_buffer[..bytes_read] {
-                    if byte == b'\n' || byte == b'\r' {
-                        // Processar comando completo
-                        if !buffer.is_empty() {
-                            let cmd_str = buffer.as_str().trim();
-                            let command = Command::from_str(cmd_str);
-                            
-                            match command {
-                                Command::Help => {
-                                    send_help_message(&mut uart).ok();
-                                }
-                                Command::Info => {
-                                    send_info_message(&mut uart, &mining_state).ok();
-                                }
-                                Command::Reset => {
-                                    send_reset_message(&mut uart, &mut mining_state).ok();
-                                }
-                                Command::Zeros(zeros) => {
-                                    mining_state.set_zeros(zeros);
-                                    send_zeros_message(&mut uart, zeros).ok();
-                                    rprintln!("Zeros configurado: {}", zeros);
-                                }
-                                Command::Entropy(entropy) => {
-                                    mining_state.set_entropy(entropy);
-                                    send_entropy_message(&mut uart, entropy).ok();
-                                    rprintln!("Entropy configurado: {}", entropy);
-                                }
-                                Command::Mine => {
-                                    if mining_state.is_ready_to_mine() {
-                                        send_mine_start_message(&mut uart, mining_state.zeros, mining_state.entropy).ok();
-                                        rprintln!("Iniciando mineração com {}...", mining_state.get_hash_algorithm().as_str());
-                                        
-                                        // Atualizar o estado do minerador atual
-                                        current_miner.update_state(mining_state);
-                                        
-                                        match current_miner.mine(&mut uart, &mut led) {
-                                            Ok(nonce) => {
-                                                mining_state.set_last_nonce(nonce);
-                                                send_mine_result_message(&mut uart, nonce).ok();
-                                                rprintln!("Mineração concluída! Nonce: {}", nonce);
-                                            }
-                                            Err(_) => {
-                                                send_mine_error_message(&mut uart, "Erro durante mineração").ok();
-                                                rprintln!("Erro na mineração");
-                                            }
-                                        }
-                                    } else {
-                                        send_mine_error_message(&mut uart, "Configure zeros e entropy primeiro").ok();
-                                        rprintln!("Mineração não configurada");
-                                    }
-                                }
-                                Command::Hash(algorithm) => {
-                                    mining_state.set_hash_algorithm(algorithm);
-                                    current_miner = MinerFactory::create_miner_for_algorithm(algorithm);
-                                    send_hash_message(&mut uart, algorithm).ok();
-                                    rprintln!("Algoritmo alterado para: {}", algorithm.as_str());
-                                }
-                                Command::HashInfo => {
-                                    send_current_hash_message(&mut uart, mining_state.get_hash_algorithm()).ok();
-                                    rprintln!("Algoritmo atual: {}", mining_state.get_hash_algorithm().as_str());
+        // Drain the RX FIFO every pass - it holds only 128 bytes and esp-hal keeps no software buffer
+        while uart.read_ready() {
+            let mut temp_buffer = [0u8; 32];
+            match uart.read(&mut temp_buffer) {
+                Ok(bytes_read) => {
+                    // Checked per byte - a `mode` command switches the protocol mid-read
+                    for &byte in &temp_buffer[..bytes_read] {
+                        receive_byte(&mut uart, &mut session, &mut console, byte);
+                    }
+                }
+                Err(error) => {
+                    // Bytes were lost - the half received line or frame is corrupt
+                    console.clear();
+                    let reply = Reply { mode: session.mode, id: None };
+                    emit(&mut uart, reply, &Event::Error { source: ErrorSource::Command, message: &"Dados perdidos na recepção, reenvie o comando" });
+                    rprintln!("Erro na recepção: {:?}", error);
+                }
+            }
+        }
+
+        if let Some(job) = session.miner.job().copied().filter(|job| job.paused_at.is_none()) {
+            // Hash one batch, then go back to the console - reports use the job's own unit and range
+            let reply = session.job_reply();
+            match session.miner.step(job.slice(session.mining_state.batch_size, MINING_SLICE_MS)) {
+                StepOutcome::Improved(result) => {
+                    emit(&mut uart, reply, &Event::MineBest { result: &result, unit: job.zero_unit });
+                }
+                StepOutcome::Finished(outcome) => {
+                    report_outcome(&mut uart, &mut session.mining_state, &job, outcome, reply);
+                }
+                StepOutcome::Running | StepOutcome::Paused | StepOutcome::Idle => {}
+            }
+
+            // Enviar progresso periodicamente - on wall-clock time, whatever the algorithm
+            let interval = session.mining_state.progress_interval_ms;
+            if interval > 0 && session.last_progress.elapsed() >= Duration::from_millis(interval as u64) {
+                if let Some(job) = session.miner.job() {
+                    emit(&mut uart, reply, &Event::MineProgress(job));
+                }
+                session.last_progress = Instant::now();
+            }
+        }
+    }
+}
+
+/// Feeds one received byte, running the command line or frame it completes
+fn receive_byte(uart: &mut Uart<Blocking>, session: &mut Session, console: &mut Console, byte: u8) {
+    match session.mode {
+        OutputMode::Text | OutputMode::Json => {
+            if (byte == b'\n' || byte == b'\r') && core::mem::take(&mut console.overflow) {
+                let reply = Reply { mode: session.mode, id: None };
+                emit(uart, reply, &Event::Error { source: ErrorSource::Command, message: &"Linha maior que o buffer, comando descartado" });
+                rprintln!("Linha descartada: mais de {} caracteres", MAX_LINE_LEN);
+            } else if byte == b'\n' || byte == b'\r' {
+                // Processar comando completo
+                if !console.buffer.is_empty() {
+                    let line = console.buffer.as_str().trim();
+                    match Request::from_line(line) {
+                        Ok(request) => {
+                            let reply = Reply { mode: session.mode, id: request.id };
+                            match Command::from_str(request.cmd) {
+                                Ok(command) => {
+                                    handle_command(uart, session, command, reply);
+                                    rprintln!("Comando processado: {}", line);
                                 }
-                                Command::Unknown(_) => {
-                                    send_unknown_command_message(&mut uart, cmd_str).ok();
+                                Err(error) => {
+                                    emit(uart, reply, &Event::parse_error(request.cmd, &error));
+                                    rprintln!("Comando inválido: {} ({})", line, error);
                                 }
                             }
-                            
-                            rprintln!("Comando processado: {}", cmd_str);
-                            buffer.clear();
                         }
-                    } else if byte >= 32 && byte <= 126 { // Printable characters
-                        if buffer.push(byte as char).is_err() {
-                            // Buffer cheio, limpar e continuar
-                            buffer.clear();
+                        Err(error) => {
+                            let reply = Reply { mode: session.mode, id: None };
+                            emit(uart, reply, &Event::Error { source: ErrorSource::Command, message: &error });
+                            rprintln!("Id inválido: {} ({})", line, error);
                         }
                     }
+                    console.buffer.clear();
+                    if session.mode == OutputMode::Binary {
+                        // Switched to binary: nothing of the text line may reach the frame reader
+                        console.reader.clear();
+                        console.pending_lf = byte == b'\r';
+                    }
+                }
+            } else if byte >= 32 && byte <= 126 && !console.overflow { // Printable characters
+                if console.buffer.push(byte as char).is_err() {
+                    // Buffer cheio - the tail must not be taken for a new command
+                    console.buffer.clear();
+                    console.overflow = true;
+                }
+            }
+        }
+        // The LF of the `mode binary\r\n` line is not part of a frame
+        OutputMode::Binary if core::mem::take(&mut console.pending_lf) && byte == b'\n' => {}
+        OutputMode::Binary => match console.reader.push(byte) {
+            Some(Ok(frame)) => {
+                let reply = Reply { mode: OutputMode::Binary, id: Some(frame.seq as u32) };
+                match Command::from_frame(&frame) {
+                    Ok(command) => {
+                        handle_command(uart, session, command, reply);
+                        rprintln!("Frame processado: tipo {:#04x}, seq {}", frame.kind, frame.seq);
+                    }
+                    Err(error) => {
+                        let source = ErrorSource::from_kind(frame.kind);
+                        emit(uart, reply, &Event::Error { source, message: &error });
+                        rprintln!("Frame inválido: tipo {:#04x} ({})", frame.kind, error);
+                    }
+                }
+                if session.mode != OutputMode::Binary {
+                    console.reader.clear();
+                }
+            }
+            Some(Err(error)) => {
+                // The seq of a corrupt frame cannot be trusted, so the NAK carries 0
+                emit(uart, Reply { mode: OutputMode::Binary, id: None }, &Event::Nak(error));
+                rprintln!("Frame rejeitado: {}", error);
+            }
+            None => {}
+        },
+    }
+}
+
+/// Runs one parsed command, text, JSON or binary, replying in the protocol it came in
+fn handle_command(uart: &mut Uart<Blocking>, session: &mut Session, command: Command, reply: Reply) {
+    let Session { mining_state, miner: current_miner, last_progress, mode, job_id } = session;
+    match command {
+        Command::Help => {
+            emit(uart, reply, &Event::Help);
+        }
+        Command::Hello => {
+            emit(uart, reply, &Event::Hello);
+        }
+        Command::Info => {
+            emit(uart, reply, &Event::Info { state: mining_state, job: current_miner.job() });
+        }
+        Command::Reset => {
+            // A job of the old configuration would keep reporting under the new one - stop it first
+            if let Ok(job) = current_miner.stop() {
+                emit(uart, Reply { id: *job_id, ..reply }, &Event::MineStopped(&job));
+                rprintln!("Mineração abortada no nonce {}", job.next_nonce);
+            }
+            mining_state.reset();
+            *current_miner = MinerFactory::create_miner_for_algorithm(mining_state.get_hash_algorithm());
+            *job_id = None;
+            emit(uart, reply, &Event::Reset { state: mining_state });
+        }
+        Command::Zeros(zeros, unit) if !ZeroChecker::is_feasible(zeros, unit.unwrap_or(mining_state.zero_unit)) => {
+            emit(uart, reply, &Event::Error { source: ErrorSource::Zeros, message: &ParseError::OutOfRange("zeros") });
+        }
+        Command::Zeros(zeros, unit) => {
+            if let Some(unit) = unit {
+                mining_state.set_zero_unit(unit);
+            }
+            mining_state.set_zeros(zeros);
+            emit(uart, reply, &Event::Zeros { zeros, unit: mining_state.zero_unit });
+            rprintln!("Zeros configurado: {} {}", zeros, mining_state.zero_unit.as_str());
+        }
+        Command::Entropy(entropy) => {
+            mining_state.set_entropy(entropy);
+            emit(uart, reply, &Event::Entropy(&entropy));
+            rprintln!("Entropy configurado: {}", Hex(&entropy));
+        }
+        Command::Farmer(farmer) => {
+            mining_state.set_farmer(farmer);
+            emit(uart, reply, &Event::Farmer(&farmer));
+            rprintln!("Farmer configurado: {}", AccountId(&farmer));
+        }
+        Command::Block(block_index) => {
+            mining_state.set_block_index(block_index);
+            emit(uart, reply, &Event::Block(block_index));
+            rprintln!("Bloco configurado: {}", block_index);
+        }
+        Command::StartMining(job) => {
+            // Applied only once the job starts - a rejected job leaves the configuration as is
+            let mut job_state = *mining_state;
+            job_state.load_job(&job);
+            if start_job(uart, current_miner, &job_state, None, reply, *job_id) {
+                *mining_state = job_state;
+                *last_progress = Instant::now();
+                *job_id = reply.id;
+                rprintln!("Job configurado: bloco {}, {} nibbles, lote {}", job.block_index, job.zeros, job.batch_size);
+            }
+        }
+        Command::Mine(budget) => {
+            if start_job(uart, current_miner, mining_state, budget, reply, *job_id) {
+                *last_progress = Instant::now();
+                *job_id = reply.id;
+            }
+        }
+        Command::Estimate => {
+            // Measured rate of the running job, or a short calibration run
+            let hashrate = match current_miner.job() {
+                Some(job) if job.hashrate() > 0 => job.hashrate(),
+                _ => current_miner.measure_hashrate(ESTIMATE_CALIBRATION_NONCES),
+            };
+            emit(uart, reply, &Event::Estimate { zeros: mining_state.zeros, unit: mining_state.zero_unit, hashrate });
+        }
+        Command::Verify(nonce) => {
+            // Current configuration, not the running job's snapshot
+            let result = current_miner.verify(mining_state, nonce);
+            let event = Event::Verify {
+                result: &result,
+                target: mining_state.zeros,
+                unit: mining_state.zero_unit,
+                algorithm: mining_state.get_hash_algorithm(),
+            };
+            emit(uart, reply, &event);
+        }
+        Command::Stop => match current_miner.stop() {
+            Ok(job) => {
+                // The job's last event goes out under its id; the stop command gets its own copy as the ack
+                emit(uart, Reply { id: *job_id, ..reply }, &Event::MineStopped(&job));
+                if reply.id != *job_id {
+                    emit(uart, reply, &Event::MineStopped(&job));
                 }
+                *job_id = None;
+                rprintln!("Mineração abortada no nonce {}", job.next_nonce);
             }
+            Err(error) => send_mining_error(uart, reply, error),
+        },
+        Command::Pause => match current_miner.pause() {
+            Ok(()) => {
+                if let Some(job) = current_miner.job() {
+                    emit(uart, reply, &Event::MinePaused(job));
+                }
+                rprintln!("Mineração pausada");
+            }
+            Err(error) => send_mining_error(uart, reply, error),
+        },
+        Command::Resume => match current_miner.resume() {
+            Ok(()) => {
+                if let Some(job) = current_miner.job() {
+                    emit(uart, reply, &Event::MineResumed(job));
+                }
+                rprintln!("Mineração retomada");
+            }
+            Err(error) => send_mining_error(uart, reply, error),
+        },
+        Command::Progress(interval) => {
+            mining_state.set_progress_interval(interval);
+            emit(uart, reply, &Event::ProgressInterval(interval));
+            rprintln!("Intervalo de progresso: {} ms", interval);
+        }
+        Command::Range(start, end) => {
+            mining_state.set_nonce_range(start, end);
+            emit(uart, reply, &Event::Range { start, end });
+            rprintln!("Faixa de nonces configurada: {}-{}", start, end);
+        }
+        Command::Hash(_) if current_miner.job().is_some() => {
+            emit(uart, reply, &Event::Error { source: ErrorSource::Hash, message: &"Mineração em andamento" });
+        }
+        Command::Hash(algorithm) => {
+            mining_state.set_hash_algorithm(algorithm);
+            *current_miner = MinerFactory::create_miner_for_algorithm(algorithm);
+            emit(uart, reply, &Event::Hash(algorithm));
+            rprintln!("Algoritmo alterado para: {}", algorithm.as_str());
         }
+        Command::Digest(algorithm, data) => {
+            let hash = digest(algorithm, &data);
+            emit(uart, reply, &Event::Digest { algorithm, len: data.len(), hash: &hash });
+        }
+        Command::Bench(..) if current_miner.job().is_some() => {
+            emit(uart, reply, &Event::Error { source: ErrorSource::Bench, message: &"Mineração em andamento" });
+        }
+        Command::Bench(algorithm, nonces) => {
+            // Only reads the job parameters - MiningState is left as is
+            let preimage = mining_state.preimage();
+            let algorithms: &[HashAlgorithm] = match &algorithm {
+                Some(algorithm) => core::slice::from_ref(algorithm),
+                None => &HashAlgorithm::ALL,
+            };
+            for &algorithm in algorithms {
+                let bench = benchmark_algorithm(algorithm, &preimage, nonces);
+                emit(uart, reply, &Event::Bench { algorithm, bench: &bench });
+                rprintln!("Bench {}: {} H/s", algorithm.as_str(), bench.hashrate());
+            }
+        }
+        Command::SelfTest => {
+            run_selftest(uart, reply);
+        }
+        Command::HashInfo => {
+            emit(uart, reply, &Event::HashInfo(mining_state.get_hash_algorithm()));
+            rprintln!("Algoritmo atual: {}", mining_state.get_hash_algorithm().as_str());
+        }
+        Command::Mode(new_mode) => {
+            // Confirmed in the protocol the command came in, then switched
+            emit(uart, reply, &Event::Mode(new_mode));
+            *mode = new_mode;
+            rprintln!("Modo do console: {}", new_mode.as_str());
+        }
+    }
+}
+
+/// Sends one event in the reply's protocol
+fn emit(uart: &mut Uart<Blocking>, reply: Reply, event: &Event) {
+    if event.emit(uart, reply.mode, reply.id).is_err() {
+        rprintln!("Falha ao enviar {}", event.name());
+    }
+}
+
+/// Sends a miner state error (nothing to stop, pause or resume)
+fn send_mining_error(uart: &mut Uart<Blocking>, reply: Reply, error: MiningError) {
+    emit(uart, reply, &Event::Error { source: ErrorSource::Mine, message: &error.as_str() });
+}
 
+/// Runs the known-answer tests, replying once per case and with a summary
+fn run_selftest(uart: &mut Uart<Blocking>, reply: Reply) -> selftest::SelfTestSummary {
+    let summary = selftest::run(|name, ok| emit(uart, reply, &Event::SelfTestCase { name, ok }));
+    emit(uart, reply, &Event::SelfTestDone(summary));
+    summary
+}
 
+/// Starts a job from the current configuration - a new job replaces a running
+/// one, whose `MINE_STOPPED` goes out under the id of the request that started it
+fn start_job(uart: &mut Uart<Blocking>, miner: &mut MinerType, mining_state: &MiningState, budget: Option<MiningBudget>, reply: Reply, running_id: Option<u32>) -> bool {
+    if !mining_state.is_ready_to_mine() {
+        send_mining_error(uart, reply, MiningError::NotConfigured);
+        rprintln!("Mineração não configurada");
+        return false;
+    }
 
-        // Small delay to not overload the system
-        let delay_start = Instant::now();
-        while delay_start.elapsed() < Duration::from_millis(50) {}
+    // Atualizar o estado do minerador atual
+    miner.update_state(*mining_state);
+    match miner.start(budget) {
+        Ok(replaced) => {
+            if let Some(job) = replaced {
+                emit(uart, Reply { id: running_id, ..reply }, &Event::MineStopped(&job));
+                rprintln!("Mineração substituída no nonce {}", job.next_nonce);
+            }
+            let event = Event::MineStart {
+                block_index: mining_state.block_index,
+                zeros: mining_state.zeros,
+                unit: mining_state.zero_unit,
+                entropy: &mining_state.entropy,
+            };
+            emit(uart, reply, &event);
+            rprintln!("Iniciando mineração com {}...", mining_state.get_hash_algorithm().as_str());
+            true
+        }
+        Err(error) => {
+            send_mining_error(uart, reply, error);
+            rprintln!("Erro na mineração");
+            false
+        }
     }
-}
\ No newline at end of file
+}
+
+/// Reports how a mining job ended, in the unit and range the job started with
+fn report_outcome(uart: &mut Uart<Blocking>, mining_state: &mut MiningState, job: &MiningJob, outcome: MiningOutcome, reply: Reply) {
+    // `hash` is refused while a job exists, so the algorithm is still the job's
+    let (unit, algorithm) = (job.zero_unit, mining_state.get_hash_algorithm());
+    match outcome {
+        MiningOutcome::Solved(result) => {
+            mining_state.set_last_nonce(result.nonce);
+            emit(uart, reply, &Event::Found { result: &result, unit, algorithm });
+            emit(uart, reply, &Event::MineResult { result: &result, unit, algorithm });
+            rprintln!("Mineração concluída! Nonce: {}", result.nonce);
+        }
+        MiningOutcome::Best(result) => {
+            mining_state.set_last_nonce(result.nonce);
+            emit(uart, reply, &Event::MineResult { result: &result, unit, algorithm });
+            rprintln!("Mineração concluída! Nonce: {}", result.nonce);
+        }
+        MiningOutcome::Exhausted(best) => {
+            let (start, end) = (job.nonce_start, job.nonce_end);
+            emit(uart, reply, &Event::MineExhausted { start, end, best: &best, unit });
+            rprintln!("Faixa de nonces esgotada: {}-{}", start, end);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! CRC module - checksums used by Stellar strkeys and serial frames
+
+/// CRC-16/XMODEM (poly 0x1021, init 0x0000, no reflection)
+pub fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
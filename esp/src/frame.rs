@@ -0,0 +1,270 @@
+//! Frame module - binary protocol framing
+//!
+//! On the wire each frame is `COBS(type, seq, payload.., crc_hi, crc_lo)`
+//! followed by a `0x00` delimiter. The CRC is CRC-16/XMODEM over type, seq
+//! and payload; COBS guarantees the delimiter never appears inside a frame,
+//! so a receiver resynchronizes on the next `0x00` after a dropped byte.
+
+use core::fmt;
+
+use crate::crc::crc16_xmodem;
+
+/// Largest payload carried by one frame
+pub const MAX_PAYLOAD: usize = 192;
+/// Type, seq, payload and CRC before COBS
+pub const MAX_FRAME: usize = MAX_PAYLOAD + 4;
+/// COBS output (one extra byte per 254) plus the delimiter
+pub const MAX_ENCODED: usize = MAX_FRAME + MAX_FRAME / 254 + 2;
+
+/// Frame types - commands from the host below 0x80, device replies and events from 0x80
+pub mod kind {
+    pub const HELP: u8 = 0x01;
+    pub const INFO: u8 = 0x02;
+    pub const RESET: u8 = 0x03;
+    pub const ZEROS: u8 = 0x04;
+    pub const ENTROPY: u8 = 0x05;
+    pub const FARMER: u8 = 0x06;
+    pub const BLOCK: u8 = 0x07;
+    pub const START_MINING: u8 = 0x08;
+    pub const MINE: u8 = 0x09;
+    pub const ESTIMATE: u8 = 0x0a;
+    pub const VERIFY: u8 = 0x0b;
+    pub const RANGE: u8 = 0x0c;
+    pub const PROGRESS: u8 = 0x0d;
+    pub const STOP: u8 = 0x0e;
+    pub const PAUSE: u8 = 0x0f;
+    pub const RESUME: u8 = 0x10;
+    pub const HASH: u8 = 0x11;
+    pub const HASH_INFO: u8 = 0x12;
+    pub const DIGEST: u8 = 0x13;
+    pub const SELFTEST: u8 = 0x14;
+    pub const BENCH: u8 = 0x15;
+    pub const MODE: u8 = 0x16;
+    pub const HELLO: u8 = 0x17;
+
+    /// Every command type, in order
+    pub const COMMANDS: [u8; 23] = [
+        HELP, INFO, RESET, ZEROS, ENTROPY, FARMER, BLOCK, START_MINING, MINE, ESTIMATE, VERIFY,
+        RANGE, PROGRESS, STOP, PAUSE, RESUME, HASH, HASH_INFO, DIGEST, SELFTEST, BENCH, MODE, HELLO,
+    ];
+
+    pub const HELP_REPLY: u8 = 0x80;
+    pub const INFO_REPLY: u8 = 0x81;
+    pub const RESET_REPLY: u8 = 0x82;
+    pub const ZEROS_SET: u8 = 0x83;
+    pub const ENTROPY_SET: u8 = 0x84;
+    pub const FARMER_SET: u8 = 0x85;
+    pub const BLOCK_SET: u8 = 0x86;
+    pub const RANGE_SET: u8 = 0x87;
+    pub const PROGRESS_SET: u8 = 0x88;
+    pub const HASH_SET: u8 = 0x89;
+    pub const HASH_CURRENT: u8 = 0x8a;
+    pub const MODE_SET: u8 = 0x8b;
+    pub const READY: u8 = 0x8c;
+    pub const HELLO_REPLY: u8 = 0x8d;
+    pub const MINE_START: u8 = 0x90;
+    pub const FOUND: u8 = 0x91;
+    pub const MINE_RESULT: u8 = 0x92;
+    pub const MINE_BEST: u8 = 0x93;
+    pub const MINE_EXHAUSTED: u8 = 0x94;
+    pub const MINE_STOPPED: u8 = 0x95;
+    pub const MINE_PAUSED: u8 = 0x96;
+    pub const MINE_RESUMED: u8 = 0x97;
+    pub const MINE_PROGRESS: u8 = 0x98;
+    pub const ESTIMATE_REPLY: u8 = 0x99;
+    pub const VERIFY_REPLY: u8 = 0x9a;
+    pub const DIGEST_REPLY: u8 = 0x9b;
+    pub const BENCH_REPLY: u8 = 0x9c;
+    pub const SELFTEST_CASE: u8 = 0x9d;
+    pub const SELFTEST_DONE: u8 = 0x9e;
+    pub const ERROR: u8 = 0xe0;
+    /// Corrupt frame - payload is the `FrameError` code
+    pub const NAK: u8 = 0xff;
+}
+
+/// Errors of a received frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameError {
+    /// COBS encoding broken (misplaced zero or code past the end)
+    Cobs,
+    /// Fewer bytes than type, seq and CRC
+    TooShort,
+    /// More bytes than the largest frame
+    TooLong,
+    /// CRC trailer does not match the contents
+    Crc { expected: u16, found: u16 },
+}
+
+impl FrameError {
+    /// Code sent in the NAK payload
+    pub fn code(&self) -> u8 {
+        match self {
+            FrameError::Cobs => 0,
+            FrameError::TooShort => 1,
+            FrameError::TooLong => 2,
+            FrameError::Crc { .. } => 3,
+        }
+    }
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::Cobs => write!(f, "COBS inválido"),
+            FrameError::TooShort => write!(f, "frame curto demais"),
+            FrameError::TooLong => write!(f, "frame longo demais"),
+            FrameError::Crc { expected, found } => {
+                write!(f, "CRC {:04x} esperado, recebido {:04x}", expected, found)
+            }
+        }
+    }
+}
+
+/// A decoded frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub kind: u8,
+    pub seq: u8,
+    pub payload: heapless::Vec<u8, MAX_PAYLOAD>,
+}
+
+impl Frame {
+    /// Decodes one frame from its COBS bytes (without the delimiter) and checks the CRC
+    pub fn decode(encoded: &[u8]) -> Result<Self, FrameError> {
+        let mut raw = [0u8; MAX_FRAME];
+        let len = cobs_decode(encoded, &mut raw)?;
+        if len < 4 {
+            return Err(FrameError::TooShort);
+        }
+
+        let (body, trailer) = raw[..len].split_at(len - 2);
+        let expected = crc16_xmodem(body);
+        let found = u16::from_be_bytes([trailer[0], trailer[1]]);
+        if expected != found {
+            return Err(FrameError::Crc { expected, found });
+        }
+
+        Ok(Self {
+            kind: body[0],
+            seq: body[1],
+            payload: heapless::Vec::from_slice(&body[2..]).map_err(|_| FrameError::TooLong)?,
+        })
+    }
+}
+
+/// Encodes a frame, delimiter included, returning the number of bytes to send
+pub fn encode(kind: u8, seq: u8, payload: &[u8], out: &mut [u8; MAX_ENCODED]) -> Result<usize, FrameError> {
+    if payload.len() > MAX_PAYLOAD {
+        return Err(FrameError::TooLong);
+    }
+
+    let mut raw = [0u8; MAX_FRAME];
+    let len = payload.len() + 4;
+    raw[0] = kind;
+    raw[1] = seq;
+    raw[2..len - 2].copy_from_slice(payload);
+    let crc = crc16_xmodem(&raw[..len - 2]);
+    raw[len - 2..len].copy_from_slice(&crc.to_be_bytes());
+
+    let encoded = cobs_encode(&raw[..len], &mut out[..MAX_ENCODED - 1]);
+    out[encoded] = 0;
+    Ok(encoded + 1)
+}
+
+/// Collects received bytes into frames, split on the `0x00` delimiter
+pub struct FrameReader {
+    buf: heapless::Vec<u8, MAX_ENCODED>,
+    overflow: bool,
+}
+
+impl Default for FrameReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameReader {
+    pub const fn new() -> Self {
+        Self {
+            buf: heapless::Vec::new(),
+            overflow: false,
+        }
+    }
+
+    /// Feeds one byte; returns a frame (or why it was rejected) at each delimiter
+    pub fn push(&mut self, byte: u8) -> Option<Result<Frame, FrameError>> {
+        if byte != 0 {
+            if self.buf.push(byte).is_err() {
+                self.overflow = true;
+            }
+            return None;
+        }
+
+        // Back-to-back delimiters are idle line, not empty frames
+        if self.buf.is_empty() && !self.overflow {
+            return None;
+        }
+        let frame = if self.overflow {
+            Err(FrameError::TooLong)
+        } else {
+            Frame::decode(&self.buf)
+        };
+        self.buf.clear();
+        self.overflow = false;
+        Some(frame)
+    }
+
+    /// Drops a partially received frame
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.overflow = false;
+    }
+}
+
+/// COBS-encodes `data` into `out` (sized by the caller), returning the encoded length
+fn cobs_encode(data: &[u8], out: &mut [u8]) -> usize {
+    let mut code_index = 0;
+    let mut code = 1u8;
+    let mut len = 1;
+    for &byte in data {
+        if byte != 0 {
+            out[len] = byte;
+            len += 1;
+            code += 1;
+        }
+        if byte == 0 || code == 0xff {
+            out[code_index] = code;
+            code_index = len;
+            len += 1;
+            code = 1;
+        }
+    }
+    out[code_index] = code;
+    len
+}
+
+/// Decodes COBS bytes into `out`, returning the decoded length
+fn cobs_decode(data: &[u8], out: &mut [u8]) -> Result<usize, FrameError> {
+    let mut len = 0;
+    let mut i = 0;
+    while i < data.len() {
+        let code = data[i] as usize;
+        if code == 0 || i + code > data.len() {
+            return Err(FrameError::Cobs);
+        }
+        for &byte in &data[i + 1..i + code] {
+            if byte == 0 {
+                return Err(FrameError::Cobs);
+            }
+            *out.get_mut(len).ok_or(FrameError::TooLong)? = byte;
+            len += 1;
+        }
+        i += code;
+        // Every block but the last, and those of 254 data bytes, ends in a zero
+        if code != 0xff && i < data.len() {
+            *out.get_mut(len).ok_or(FrameError::TooLong)? = 0;
+            len += 1;
+        }
+    }
+    Ok(len)
+}
@@ -0,0 +1,99 @@
+//! Hex module - decoding and encoding without allocation
+//!
+//! Used for the 32-byte job parameters (entropy, farmer) and for printing
+//! digests in results.
+
+use core::fmt;
+
+/// Hex decoding errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HexError {
+    /// Wrong number of hex digits
+    InvalidLength { expected: usize, found: usize },
+    /// Character that is not a hex digit, with its position
+    InvalidChar { position: usize, found: char },
+    /// Odd number of hex digits
+    OddLength { found: usize },
+    /// More bytes than the output buffer holds
+    TooLong { max: usize, found: usize },
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexError::InvalidLength { expected, found } => {
+                write!(f, "esperados {} caracteres hex, recebidos {}", expected, found)
+            }
+            HexError::InvalidChar { position, found } => {
+                write!(f, "caractere '{}' inválido na posição {}", found, position)
+            }
+            HexError::OddLength { found } => {
+                write!(f, "número ímpar de caracteres hex ({})", found)
+            }
+            HexError::TooLong { max, found } => {
+                write!(f, "máximo de {} caracteres hex, recebidos {}", max, found)
+            }
+        }
+    }
+}
+
+/// Decodes exactly `N` bytes from hex (an optional `0x` prefix is accepted)
+pub fn decode_array<const N: usize>(s: &str) -> Result<[u8; N], HexError> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    let found = s.len();
+    if found != N * 2 {
+        return Err(HexError::InvalidLength { expected: N * 2, found });
+    }
+
+    let mut bytes = [0u8; N];
+    decode_digits(s.as_bytes(), &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Decodes a variable number of bytes into `buf`, returning the decoded part
+pub fn decode_into<'a>(s: &str, buf: &'a mut [u8]) -> Result<&'a [u8], HexError> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    let found = s.len();
+    if found % 2 != 0 {
+        return Err(HexError::OddLength { found });
+    }
+    if found > buf.len() * 2 {
+        return Err(HexError::TooLong { max: buf.len() * 2, found });
+    }
+
+    let bytes = &mut buf[..found / 2];
+    decode_digits(s.as_bytes(), bytes)?;
+    Ok(bytes)
+}
+
+/// Decodes pairs of hex digits into `bytes` (`digits` holds exactly twice as many)
+fn decode_digits(digits: &[u8], bytes: &mut [u8]) -> Result<(), HexError> {
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let high = nibble(digits[2 * i], 2 * i)?;
+        let low = nibble(digits[2 * i + 1], 2 * i + 1)?;
+        *byte = (high << 4) | low;
+    }
+    Ok(())
+}
+
+/// Converts one ASCII hex digit
+fn nibble(digit: u8, position: usize) -> Result<u8, HexError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(HexError::InvalidChar { position, found: digit as char }),
+    }
+}
+
+/// Formats bytes as lowercase hex
+pub struct Hex<'a>(pub &'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+//! JSON module - one-line JSON objects without allocation
+//!
+//! Only what the device output needs: a flat object with a `type` field,
+//! string, number, bool and null values and arrays of strings. Every value
+//! goes straight to the writer, so nothing is buffered.
+
+use core::fmt::{self, Display, Write};
+
+use crate::hex::Hex;
+
+/// Writes one JSON object, key by key
+pub struct JsonObject<'a, W: Write> {
+    out: &'a mut W,
+}
+
+impl<'a, W: Write> JsonObject<'a, W> {
+    /// Opens the object with its `type` field
+    pub fn begin(out: &'a mut W, kind: &str) -> Result<Self, fmt::Error> {
+        out.write_str("{\"type\":")?;
+        write_string(out, kind)?;
+        Ok(Self { out })
+    }
+
+    /// Writes a string field, escaped
+    pub fn str(&mut self, key: &str, value: &str) -> fmt::Result {
+        self.display(key, value)
+    }
+
+    /// Writes any `Display` value as an escaped string field
+    pub fn display<T: Display>(&mut self, key: &str, value: T) -> fmt::Result {
+        self.key(key)?;
+        self.out.write_char('"')?;
+        write!(Escaped(self.out), "{}", value)?;
+        self.out.write_char('"')
+    }
+
+    /// Writes bytes as a lowercase hex string field
+    pub fn hex(&mut self, key: &str, bytes: &[u8]) -> fmt::Result {
+        self.display(key, Hex(bytes))
+    }
+
+    /// Writes an unsigned number field
+    pub fn number<N: Into<u128>>(&mut self, key: &str, value: N) -> fmt::Result {
+        self.key(key)?;
+        write!(self.out, "{}", value.into())
+    }
+
+    /// Writes a number field, or `null` when there is none
+    pub fn optional<N: Into<u128>>(&mut self, key: &str, value: Option<N>) -> fmt::Result {
+        match value {
+            Some(value) => self.number(key, value),
+            None => {
+                self.key(key)?;
+                self.out.write_str("null")
+            }
+        }
+    }
+
+    pub fn bool(&mut self, key: &str, value: bool) -> fmt::Result {
+        self.key(key)?;
+        self.out.write_str(if value { "true" } else { "false" })
+    }
+
+    /// Writes an array of strings
+    pub fn str_array(&mut self, key: &str, values: &[&str]) -> fmt::Result {
+        self.key(key)?;
+        self.out.write_char('[')?;
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.out.write_char(',')?;
+            }
+            write_string(self.out, value)?;
+        }
+        self.out.write_char(']')
+    }
+
+    /// Closes the object and ends the line
+    pub fn end(self) -> fmt::Result {
+        self.out.write_str("}\r\n")
+    }
+
+    fn key(&mut self, key: &str) -> fmt::Result {
+        self.out.write_char(',')?;
+        write_string(self.out, key)?;
+        self.out.write_char(':')
+    }
+}
+
+/// Writes a quoted, escaped string
+fn write_string<W: Write>(out: &mut W, s: &str) -> fmt::Result {
+    out.write_char('"')?;
+    Escaped(out).write_str(s)?;
+    out.write_char('"')
+}
+
+/// Escapes quotes, backslashes and control characters on the way through
+struct Escaped<'a, W: Write>(&'a mut W);
+
+impl<W: Write> Write for Escaped<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                c if (c as u32) < 0x20 => write!(self.0, "\\u{:04x}", c as u32)?,
+                c => self.0.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
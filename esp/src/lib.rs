@@ -1,4 +1,13 @@
 #![no_std]
 
 pub mod msg;
-pub mod mining;
\ No newline at end of file
+pub mod mining;
+pub mod midstate;
+pub mod hex;
+pub mod crc;
+pub mod strkey;
+pub mod selftest;
+pub mod wire;
+pub mod frame;
+pub mod json;
+pub mod time;
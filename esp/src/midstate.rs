@@ -0,0 +1,190 @@
+//! Midstate module - per-job precomputation for the KALE preimage
+//!
+//! Between two nonces only the 8 nonce bytes of the 76-byte preimage change.
+//! Everything derived from the constant bytes is computed once per job:
+//! - Keccak-256: the whole absorbed (and padded) block; each hash only
+//!   rewrites the two lanes holding the nonce before running keccak-f
+//! - SHA-256: the constant words of the first block, the first round and
+//!   the complete message schedule of the second block
+
+use crate::mining::KalePreimage;
+
+/// Keccak-256 rate in bytes (1088 bits)
+const KECCAK_RATE: usize = 136;
+
+/// Keccak-256 midstate - the padded preimage already laid out in lanes
+#[derive(Debug, Clone, Copy)]
+pub struct KeccakMidstate {
+    lanes: [u64; 25],
+}
+
+impl KeccakMidstate {
+    /// Absorbs the constant part of the preimage
+    pub fn new(preimage: &KalePreimage) -> Self {
+        // The 76 bytes fit in a single block: pad it once, with the nonce zeroed
+        let mut block = [0u8; KECCAK_RATE];
+        block[..76].copy_from_slice(&preimage.to_bytes(0));
+        block[76] = 0x01;
+        block[KECCAK_RATE - 1] |= 0x80;
+
+        let mut lanes = [0u64; 25];
+        for (lane, bytes) in lanes.iter_mut().zip(block.chunks_exact(8)) {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(bytes);
+            *lane = u64::from_le_bytes(word);
+        }
+        Self { lanes }
+    }
+
+    /// Hashes the preimage for a nonce
+    pub fn hash(&self, nonce: u64) -> [u8; 32] {
+        // Nonce bytes [4-11] (big-endian) land in the high half of lane 0
+        // and the low half of lane 1 (lanes are little-endian)
+        let mut lanes = self.lanes;
+        lanes[0] |= (((nonce >> 32) as u32).swap_bytes() as u64) << 32;
+        lanes[1] |= (nonce as u32).swap_bytes() as u64;
+        keccak::f1600(&mut lanes);
+
+        let mut hash = [0u8; 32];
+        for (bytes, lane) in hash.chunks_exact_mut(8).zip(lanes.iter()) {
+            bytes.copy_from_slice(&lane.to_le_bytes());
+        }
+        hash
+    }
+}
+
+/// SHA-256 initial hash values
+const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// SHA-256 round constants
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 midstate - constant message words and the second block schedule
+#[derive(Debug, Clone, Copy)]
+pub struct Sha256Midstate {
+    /// First block words; only words 1 and 2 (the nonce) change
+    first_block: [u32; 16],
+    /// Working variables after round 0, which only reads word 0 (block index)
+    first_round: [u32; 8],
+    /// Full message schedule of the second block (farmer tail + padding)
+    second_schedule: [u32; 64],
+}
+
+impl Sha256Midstate {
+    /// Precomputes everything that does not depend on the nonce
+    pub fn new(preimage: &KalePreimage) -> Self {
+        let bytes = preimage.to_bytes(0);
+
+        // 76 bytes span two blocks: [0-63] and [64-75] + padding + bit length
+        let mut second = [0u8; 64];
+        second[..12].copy_from_slice(&bytes[64..]);
+        second[12] = 0x80;
+        second[56..].copy_from_slice(&((bytes.len() as u64) * 8).to_be_bytes());
+
+        let mut first_block = [0u32; 16];
+        load_words(&mut first_block, &bytes[..64]);
+
+        let mut second_schedule = [0u32; 64];
+        load_words(&mut second_schedule[..16], &second);
+        expand_schedule(&mut second_schedule);
+
+        let mut schedule = [0u32; 64];
+        schedule[..16].copy_from_slice(&first_block);
+        let mut first_round = SHA256_IV;
+        run_rounds(&mut first_round, &schedule, 0..1);
+
+        Self {
+            first_block,
+            first_round,
+            second_schedule,
+        }
+    }
+
+    /// Hashes the preimage for a nonce
+    pub fn hash(&self, nonce: u64) -> [u8; 32] {
+        let mut schedule = [0u32; 64];
+        schedule[..16].copy_from_slice(&self.first_block);
+        schedule[1] = (nonce >> 32) as u32;
+        schedule[2] = nonce as u32;
+        expand_schedule(&mut schedule);
+
+        let mut vars = self.first_round;
+        run_rounds(&mut vars, &schedule, 1..64);
+        let mut state = SHA256_IV;
+        add_words(&mut state, &vars);
+
+        let mut vars = state;
+        run_rounds(&mut vars, &self.second_schedule, 0..64);
+        add_words(&mut state, &vars);
+
+        let mut hash = [0u8; 32];
+        for (bytes, word) in hash.chunks_exact_mut(4).zip(state.iter()) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        hash
+    }
+}
+
+/// Loads big-endian message words
+fn load_words(words: &mut [u32], bytes: &[u8]) {
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+}
+
+/// Expands words 16..64 of the message schedule
+fn expand_schedule(w: &mut [u32; 64]) {
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+}
+
+/// Runs the given SHA-256 rounds on the working variables
+fn run_rounds(vars: &mut [u32; 8], w: &[u32; 64], rounds: core::ops::Range<usize>) {
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *vars;
+    for i in rounds {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(SHA256_K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+    *vars = [a, b, c, d, e, f, g, h];
+}
+
+/// Adds the working variables into the hash state
+fn add_words(state: &mut [u32; 8], vars: &[u32; 8]) {
+    for (word, var) in state.iter_mut().zip(vars.iter()) {
+        *word = word.wrapping_add(*var);
+    }
+}
@@ -9,28 +9,112 @@
 
 use sha2::{Digest, Sha256};
 use sha3::{Keccak256};
-use crate::msg::HashAlgorithm;
+use crate::midstate::{KeccakMidstate, Sha256Midstate};
+use crate::msg::{HashAlgorithm, MiningBudget, ZeroUnit};
+use crate::time::{Duration, Instant};
 
-use core::fmt::Write;
+/// Size of the KALE `work()` preimage in bytes
+pub const KALE_PREIMAGE_LEN: usize = 76;
+
+/// Constant part of the KALE preimage - everything except the nonce
+///
+/// Layout hashed by the contract (76 bytes):
+/// - `[0-3]`   block index (big-endian)
+/// - `[4-11]`  nonce (big-endian)
+/// - `[12-43]` entropy (previous block hash)
+/// - `[44-75]` farmer address (raw ed25519 public key)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct KalePreimage {
+    pub block_index: u32,
+    pub entropy: [u8; 32],
+    pub farmer: [u8; 32],
+}
+
+impl KalePreimage {
+    /// Creates a new preimage with the job parameters
+    pub fn new(block_index: u32, entropy: [u8; 32], farmer: [u8; 32]) -> Self {
+        Self {
+            block_index,
+            entropy,
+            farmer,
+        }
+    }
+
+    /// Serializes the preimage for a given nonce, exactly as `work()` does
+    pub fn to_bytes(&self, nonce: u64) -> [u8; KALE_PREIMAGE_LEN] {
+        let mut bytes = [0u8; KALE_PREIMAGE_LEN];
+        bytes[..4].copy_from_slice(&self.block_index.to_be_bytes());
+        bytes[4..12].copy_from_slice(&nonce.to_be_bytes());
+        bytes[12..44].copy_from_slice(&self.entropy);
+        bytes[44..].copy_from_slice(&self.farmer);
+        bytes
+    }
+}
+
+/// Default number of nonces hashed per superloop pass
+pub const DEFAULT_BATCH_SIZE: u32 = 256;
+
+/// Largest batch a job may ask for - a pass is also cut to `MiningJob::slice`
+pub const MAX_BATCH_SIZE: u32 = 1024;
+
+/// Nonces of a pass while the job has no hashrate measured yet
+const WARMUP_NONCES: u32 = 16;
+
+/// Complete job parameters, as sent in one piece by `START_MINING`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JobParams {
+    pub block_index: u32,
+    pub entropy: [u8; 32],
+    pub farmer: [u8; 32],
+    /// Difficulty in leading zero nibbles, as the KALE contract counts it
+    pub zeros: u8,
+    pub batch_size: u32,
+}
+
+impl JobParams {
+    /// Unit of `zeros` - fixed, whatever `zeros` set before
+    pub const ZERO_UNIT: ZeroUnit = ZeroUnit::Nibbles;
+}
 
 /// Mining state - stores current configurations
 #[derive(Debug, Clone, Copy)]
 pub struct MiningState {
     pub zeros: u8,
-    pub entropy: u8,
+    pub zero_unit: ZeroUnit,
+    /// Index of the block being mined
+    pub block_index: u32,
+    /// Previous block hash
+    pub entropy: [u8; 32],
+    /// Farmer address as the raw ed25519 public key
+    pub farmer: [u8; 32],
     pub is_configured: bool,
-    pub last_nonce: Option<u32>,
+    pub last_nonce: Option<u64>,
     pub hash_algorithm: HashAlgorithm,
+    /// First nonce of the range assigned by the host
+    pub nonce_start: u64,
+    /// Last nonce (inclusive) of the range assigned by the host
+    pub nonce_end: u64,
+    /// Wall-clock interval between progress messages, 0 disables them
+    pub progress_interval_ms: u32,
+    /// Nonces hashed per superloop pass - small enough to keep the console responsive
+    pub batch_size: u32,
 }
 
 impl Default for MiningState {
     fn default() -> Self {
         Self {
             zeros: 0,
-            entropy: 0,
+            zero_unit: ZeroUnit::Bytes,
+            block_index: 0,
+            entropy: [0u8; 32],
+            farmer: [0u8; 32],
             is_configured: false,
             last_nonce: None,
             hash_algorithm: HashAlgorithm::Keccak256,
+            nonce_start: 0,
+            nonce_end: u64::MAX,
+            progress_interval_ms: 1000,
+            batch_size: DEFAULT_BATCH_SIZE,
         }
     }
 }
@@ -52,15 +136,49 @@ impl MiningState {
         self.update_configuration_status();
     }
     
+    /// Sets the unit used to interpret `zeros`
+    pub fn set_zero_unit(&mut self, unit: ZeroUnit) {
+        self.zero_unit = unit;
+    }
+    
     /// Define o valor de entropy
-    pub fn set_entropy(&mut self, entropy: u8) {
+    pub fn set_entropy(&mut self, entropy: [u8; 32]) {
         self.entropy = entropy;
         self.update_configuration_status();
     }
     
-    /// Checks if mining is configured (zeros and entropy defined)
+    /// Sets the index of the block being mined
+    pub fn set_block_index(&mut self, block_index: u32) {
+        self.block_index = block_index;
+    }
+    
+    /// Sets the farmer address (raw public key)
+    pub fn set_farmer(&mut self, farmer: [u8; 32]) {
+        self.farmer = farmer;
+        self.update_configuration_status();
+    }
+    
+    /// Replaces all job parameters at once - the fields are validated by the parser,
+    /// the batch is still capped so no caller can stall the console
+    pub fn load_job(&mut self, job: &JobParams) {
+        self.block_index = job.block_index;
+        self.entropy = job.entropy;
+        self.farmer = job.farmer;
+        self.zeros = job.zeros;
+        self.zero_unit = JobParams::ZERO_UNIT;
+        self.batch_size = job.batch_size.min(MAX_BATCH_SIZE);
+        self.update_configuration_status();
+    }
+    
+    /// Checks if mining is configured (zeros, entropy and farmer defined) -
+    /// hashes bound to the all-zero farmer key could never be submitted
     fn update_configuration_status(&mut self) {
-        self.is_configured = self.zeros > 0 && self.entropy > 0;
+        self.is_configured = self.zeros > 0 && self.entropy != [0u8; 32] && self.farmer != [0u8; 32];
+    }
+    
+    /// Builds the constant part of the KALE preimage from the job parameters
+    pub fn preimage(&self) -> KalePreimage {
+        KalePreimage::new(self.block_index, self.entropy, self.farmer)
     }
     
     /// Checks if ready to mine
@@ -68,8 +186,19 @@ impl MiningState {
         self.is_configured
     }
     
+    /// Sets the nonce range to search - lets several boards split the nonce space
+    pub fn set_nonce_range(&mut self, start: u64, end: u64) {
+        self.nonce_start = start;
+        self.nonce_end = end;
+    }
+    
+    /// Sets the interval between progress messages
+    pub fn set_progress_interval(&mut self, interval_ms: u32) {
+        self.progress_interval_ms = interval_ms;
+    }
+    
     /// Sets the last found nonce
-    pub fn set_last_nonce(&mut self, nonce: u32) {
+    pub fn set_last_nonce(&mut self, nonce: u64) {
         self.last_nonce = Some(nonce);
     }
     
@@ -86,18 +215,40 @@ impl MiningState {
 
 /// Trait for hash operations - allows extensibility
 pub trait Hasher {
-    fn hash(&self, data: u8, nonce: u32) -> [u8; 32];
+    /// Precomputed state for the constant part of the preimage
+    type Midstate;
+
+    /// Hashes an arbitrary byte slice
+    fn digest(&self, data: &[u8]) -> [u8; 32];
+
+    /// Hashes the 76-byte KALE preimage for the given nonce
+    fn hash(&self, preimage: &KalePreimage, nonce: u64) -> [u8; 32] {
+        self.digest(&preimage.to_bytes(nonce))
+    }
+
+    /// Precomputes the constant part of the preimage, once per job
+    fn prepare(&self, preimage: &KalePreimage) -> Self::Midstate;
+
+    /// Hashes the preimage for the given nonce from a prepared midstate
+    fn hash_prepared(&self, midstate: &Self::Midstate, nonce: u64) -> [u8; 32];
 }
 
 /// SHA256 hasher implementation
 pub struct Sha256Hasher;
 
 impl Hasher for Sha256Hasher {
-    fn hash(&self, data: u8, nonce: u32) -> [u8; 32] {
-        let mut hasher = Sha256::new();
-        hasher.update(data.to_be_bytes());
-        hasher.update(&nonce.to_le_bytes());
-        hasher.finalize().into()
+    type Midstate = Sha256Midstate;
+
+    fn digest(&self, data: &[u8]) -> [u8; 32] {
+        Sha256::digest(data).into()
+    }
+
+    fn prepare(&self, preimage: &KalePreimage) -> Sha256Midstate {
+        Sha256Midstate::new(preimage)
+    }
+
+    fn hash_prepared(&self, midstate: &Sha256Midstate, nonce: u64) -> [u8; 32] {
+        midstate.hash(nonce)
     }
 }
 
@@ -105,11 +256,76 @@ impl Hasher for Sha256Hasher {
 pub struct Keccak256Hasher;
 
 impl Hasher for Keccak256Hasher {
-    fn hash(&self, data: u8, nonce: u32) -> [u8; 32] {
-        let mut hasher = Keccak256::new();
-        hasher.update(data.to_be_bytes());
-        hasher.update(&nonce.to_le_bytes());
-        hasher.finalize().into()
+    type Midstate = KeccakMidstate;
+
+    fn digest(&self, data: &[u8]) -> [u8; 32] {
+        Keccak256::digest(data).into()
+    }
+
+    fn prepare(&self, preimage: &KalePreimage) -> KeccakMidstate {
+        KeccakMidstate::new(preimage)
+    }
+
+    fn hash_prepared(&self, midstate: &KeccakMidstate, nonce: u64) -> [u8; 32] {
+        midstate.hash(nonce)
+    }
+}
+
+/// Hashes arbitrary bytes with the given algorithm - same code path as mining
+pub fn digest(algorithm: HashAlgorithm, data: &[u8]) -> [u8; 32] {
+    match algorithm {
+        HashAlgorithm::Sha256 => Sha256Hasher.digest(data),
+        HashAlgorithm::Keccak256 => Keccak256Hasher.digest(data),
+    }
+}
+
+/// Timing of a benchmark run
+#[derive(Debug, Clone, Copy)]
+pub struct Benchmark {
+    pub nonces: u32,
+    pub elapsed: Duration,
+}
+
+impl Benchmark {
+    /// Hashes per second
+    pub fn hashrate(&self) -> u64 {
+        let micros = self.elapsed.as_micros();
+        if micros == 0 {
+            return 0;
+        }
+        (self.nonces as u128 * 1_000_000 / micros as u128) as u64
+    }
+    
+    /// Nanoseconds per hash
+    pub fn nanos_per_hash(&self) -> u64 {
+        if self.nonces == 0 {
+            return 0;
+        }
+        (self.elapsed.as_micros() as u128 * 1000 / self.nonces as u128) as u64
+    }
+}
+
+/// Hashes `nonces` nonces of a preimage through the mining (midstate) path
+pub fn benchmark<H: Hasher>(hasher: &H, preimage: &KalePreimage, nonces: u32) -> Benchmark {
+    let midstate = hasher.prepare(preimage);
+    let started = Instant::now();
+    let mut sink = 0u8;
+    for nonce in 0..nonces as u64 {
+        sink ^= hasher.hash_prepared(&midstate, nonce)[0];
+    }
+    core::hint::black_box(sink);
+    
+    Benchmark {
+        nonces,
+        elapsed: started.elapsed(),
+    }
+}
+
+/// Runs `benchmark` with the hasher of the given algorithm
+pub fn benchmark_algorithm(algorithm: HashAlgorithm, preimage: &KalePreimage, nonces: u32) -> Benchmark {
+    match algorithm {
+        HashAlgorithm::Sha256 => benchmark(&Sha256Hasher, preimage, nonces),
+        HashAlgorithm::Keccak256 => benchmark(&Keccak256Hasher, preimage, nonces),
     }
 }
 
@@ -117,15 +333,55 @@ impl Hasher for Keccak256Hasher {
 pub struct ZeroChecker;
 
 impl ZeroChecker {
+    /// Counts the leading zero bits of a hash
+    pub fn leading_zero_bits(hash: &[u8; 32]) -> u32 {
+        let mut bits = 0;
+        for &byte in hash {
+            bits += byte.leading_zeros();
+            if byte != 0 {
+                break;
+            }
+        }
+        bits
+    }
+
+    /// Counts the leading zero nibbles (hex digits) of a hash, as KALE scores work
+    pub fn leading_zero_nibbles(hash: &[u8; 32]) -> u32 {
+        Self::leading_zero_bits(hash) / 4
+    }
+
+    /// Counts the leading zero bytes of a hash
+    pub fn leading_zero_bytes(hash: &[u8; 32]) -> u32 {
+        Self::leading_zero_bits(hash) / 8
+    }
+
+    /// Counts the leading zeros of a hash in the given unit
+    pub fn count_zeros(hash: &[u8; 32], unit: ZeroUnit) -> u32 {
+        match unit {
+            ZeroUnit::Bits => Self::leading_zero_bits(hash),
+            ZeroUnit::Nibbles => Self::leading_zero_nibbles(hash),
+            ZeroUnit::Bytes => Self::leading_zero_bytes(hash),
+        }
+    }
+
     /// Checks if hash has the required number of zeros at the beginning
-    pub fn check_zeros(hash: &[u8; 32], required_zeros: u8) -> bool {
-        // Verifica bytes completos (como no exemplo funcional)
-        let zeros_to_check = required_zeros as usize;
-        if zeros_to_check > hash.len() {
-            return false;
+    pub fn check_zeros(hash: &[u8; 32], required_zeros: u8, unit: ZeroUnit) -> bool {
+        Self::count_zeros(hash, unit) >= required_zeros as u32
+    }
+
+    /// Checks that a 256-bit digest can have the required zeros at all
+    pub fn is_feasible(required_zeros: u8, unit: ZeroUnit) -> bool {
+        required_zeros as u32 <= unit.max_zeros()
+    }
+
+    /// Expected number of hashes to find the required zeros (2^bits), saturating
+    pub fn expected_hashes(required_zeros: u8, unit: ZeroUnit) -> u128 {
+        let bits = required_zeros as u32 * unit.bits();
+        if bits >= u128::BITS {
+            u128::MAX
+        } else {
+            1u128 << bits
         }
-        
-        hash[..zeros_to_check].iter().all(|&byte| byte == 0u8)
     }
 }
 
@@ -133,6 +389,10 @@ impl ZeroChecker {
 pub struct Miner<H: Hasher> {
     hasher: H,
     state: MiningState,
+    job: Option<MiningJob>,
+    midstate: Option<H::Midstate>,
+    /// Set by `stop` until the next job - the last job was aborted, not finished
+    aborted: bool,
 }
 
 impl<H: Hasher> Miner<H> {
@@ -141,61 +401,309 @@ impl<H: Hasher> Miner<H> {
         Self {
             hasher,
             state: MiningState::new(),
+            job: None,
+            midstate: None,
+            aborted: false,
         }
     }
     
-    /// Updates mining state
+    /// Updates mining state - takes effect on the next job
     pub fn update_state(&mut self, state: MiningState) {
         self.state = state;
     }
     
-    /// Executes mining
-    pub fn mine<W, L>(&self, uart: &mut W, led: &mut L) -> Result<u32, MiningError>
-    where
-        W: Write,
-        L: embedded_hal::digital::StatefulOutputPin,
-    {
+    /// Starts a job over the assigned nonce range
+    ///
+    /// Without a budget the job stops at the first nonce that meets the target;
+    /// with one it searches the whole budget and keeps the best hash found.
+    /// A job still running or paused is replaced and returned, so its end can
+    /// still be reported.
+    pub fn start(&mut self, budget: Option<MiningBudget>) -> Result<Option<MiningJob>, MiningError> {
         if !self.state.is_ready_to_mine() {
             return Err(MiningError::NotConfigured);
         }
+        if self.state.nonce_start > self.state.nonce_end {
+            return Err(MiningError::InvalidRange);
+        }
+        if !ZeroChecker::is_feasible(self.state.zeros, self.state.zero_unit) {
+            return Err(MiningError::InfeasibleDifficulty);
+        }
         
-        let mut nonce = 0u32;
-        let ping_interval = 50000u32;
-        let led_toggle_interval = 10000u32; // Blink LED every 10k iterations
-        
-        loop {
-            let hash = self.hasher.hash(self.state.entropy, nonce);
-            
-            // Blink LED during mining
-            if nonce % led_toggle_interval == 0 {
-                led.toggle().ok();
+        self.midstate = Some(self.hasher.prepare(&self.state.preimage()));
+        self.aborted = false;
+        let replaced = self.job.replace(MiningJob {
+            budget,
+            block_index: self.state.block_index,
+            zeros: self.state.zeros,
+            zero_unit: self.state.zero_unit,
+            nonce_start: self.state.nonce_start,
+            nonce_end: self.state.nonce_end,
+            next_nonce: self.state.nonce_start,
+            tested: 0,
+            best: None,
+            started: Instant::now(),
+            paused_at: None,
+        });
+        Ok(replaced)
+    }
+    
+    /// Pauses the running job - nothing is hashed until `resume`
+    pub fn pause(&mut self) -> Result<(), MiningError> {
+        match self.job.as_mut() {
+            Some(job) if job.paused_at.is_none() => {
+                job.paused_at = Some(Instant::now());
+                Ok(())
             }
+            Some(_) => Err(MiningError::NotRunning),
+            None => Err(self.no_job_error()),
+        }
+    }
+    
+    /// Resumes a paused job from the exact nonce where it stopped
+    pub fn resume(&mut self) -> Result<(), MiningError> {
+        match self.job.as_mut() {
+            Some(job) => match job.paused_at.take() {
+                Some(paused_at) => {
+                    // Time spent paused does not count against a time budget
+                    job.started += paused_at.elapsed();
+                    Ok(())
+                }
+                None => Err(MiningError::NotPaused),
+            },
+            None => Err(self.no_job_error()),
+        }
+    }
+    
+    /// Aborts the job and returns the work done so far
+    pub fn stop(&mut self) -> Result<MiningJob, MiningError> {
+        let job = self.job.take().ok_or(self.no_job_error())?;
+        self.aborted = true;
+        Ok(job)
+    }
+    
+    /// Why there is no job to act on - `Aborted` right after a `stop`
+    fn no_job_error(&self) -> MiningError {
+        if self.aborted {
+            MiningError::Aborted
+        } else {
+            MiningError::NotRunning
+        }
+    }
+    
+    /// Tests up to `batch_size` nonces of the running job
+    ///
+    /// Returns early on a new best hash in best-so-far mode so every
+    /// improvement can be reported as it happens.
+    pub fn step(&mut self, batch_size: u32) -> StepOutcome {
+        let (Some(job), Some(midstate)) = (self.job.as_mut(), self.midstate.as_ref()) else {
+            return StepOutcome::Idle;
+        };
+        if job.paused_at.is_some() {
+            return StepOutcome::Paused;
+        }
+        
+        // Only the job's snapshot counts - the state may already hold the next job
+        for _ in 0..batch_size {
+            let nonce = job.next_nonce;
+            let hash = self.hasher.hash_prepared(midstate, nonce);
+            let zeros = ZeroChecker::count_zeros(&hash, job.zero_unit);
+            let result = MiningResult { block_index: job.block_index, nonce, hash, zeros };
+            job.tested += 1;
             
-            // Enviar ping periodicamente
-            if nonce % ping_interval == 0 {
-                let _ = write!(uart, "MINING: nonce={}, entropy={}\r\n", nonce, self.state.entropy);
+            let improved = job.best.is_none_or(|best| zeros > best.zeros);
+            if improved {
+                job.best = Some(result);
             }
+            let best = job.best.unwrap_or(result);
             
-            if ZeroChecker::check_zeros(&hash, self.state.zeros) {
-                let _ = write!(uart, "FOUND: nonce={}, hash={:02x?}\r\n", nonce, &hash[..8]);
-                return Ok(nonce);
+            let outcome = if job.budget.is_none() && zeros >= job.zeros as u32 {
+                Some(MiningOutcome::Solved(result))
+            } else if matches!(job.budget, Some(MiningBudget::Nonces(limit)) if job.tested >= limit) {
+                Some(MiningOutcome::Best(best))
+            } else if nonce == job.nonce_end {
+                // The assigned range is done - a normal outcome, the host hands out the next one
+                Some(MiningOutcome::Exhausted(best))
+            } else {
+                None
+            };
+            if let Some(outcome) = outcome {
+                self.job = None;
+                return StepOutcome::Finished(outcome);
             }
             
-            nonce = nonce.wrapping_add(1);
+            job.next_nonce += 1;
             
-            // Protection against infinite overflow
-            if nonce == 0 {
-                return Err(MiningError::Overflow);
+            if improved && job.budget.is_some() {
+                return StepOutcome::Improved(result);
+            }
+        }
+        
+        // Reading the clock once per batch is enough
+        if let (Some(MiningBudget::Millis(limit)), Some(best)) = (job.budget, job.best) {
+            if job.started.elapsed() >= Duration::from_millis(limit as u64) {
+                self.job = None;
+                return StepOutcome::Finished(MiningOutcome::Best(best));
             }
         }
+        
+        StepOutcome::Running
+    }
+    
+    /// Checks if a job is in progress and not paused
+    pub fn is_running(&self) -> bool {
+        self.job.is_some_and(|job| job.paused_at.is_none())
+    }
+    
+    /// Gets the progress of the current job, running or paused
+    pub fn job(&self) -> Option<&MiningJob> {
+        self.job.as_ref()
+    }
+    
+    /// Recomputes one nonce over the given job parameters - no state is touched
+    pub fn verify(&self, state: &MiningState, nonce: u64) -> MiningResult {
+        let hash = self.hasher.hash(&state.preimage(), nonce);
+        MiningResult {
+            block_index: state.block_index,
+            nonce,
+            hash,
+            zeros: ZeroChecker::count_zeros(&hash, state.zero_unit),
+        }
+    }
+    
+    /// Hashes `nonces` nonces of the configured preimage and returns the
+    /// measured hashrate in H/s - the job, if any, is left untouched
+    pub fn measure_hashrate(&self, nonces: u32) -> u64 {
+        benchmark(&self.hasher, &self.state.preimage(), nonces).hashrate()
     }
 }
 
+/// Progress of a running job
+#[derive(Debug, Clone, Copy)]
+pub struct MiningJob {
+    pub budget: Option<MiningBudget>,
+    /// Block being mined
+    pub block_index: u32,
+    /// Target of the job
+    pub zeros: u8,
+    pub zero_unit: ZeroUnit,
+    /// Nonce range assigned to the job (inclusive)
+    pub nonce_start: u64,
+    pub nonce_end: u64,
+    /// Next nonce to be tested
+    pub next_nonce: u64,
+    /// Nonces tested so far
+    pub tested: u64,
+    pub best: Option<MiningResult>,
+    pub started: Instant,
+    /// Set while the job is paused
+    pub paused_at: Option<Instant>,
+}
+
+impl MiningJob {
+    /// Time spent hashing, not counting the current pause
+    pub fn elapsed(&self) -> Duration {
+        match self.paused_at {
+            Some(paused_at) => paused_at - self.started,
+            None => self.started.elapsed(),
+        }
+    }
+    
+    /// Measured hashrate in hashes per second
+    pub fn hashrate(&self) -> u64 {
+        let micros = self.elapsed().as_micros();
+        if micros == 0 {
+            return 0;
+        }
+        (self.tested as u128 * 1_000_000 / micros as u128) as u64
+    }
+    
+    /// Nonces to hash in one pass so it lasts about `millis` at the measured
+    /// hashrate - the console is only read between passes, and the 128-byte
+    /// UART RX FIFO fills in about 11 ms at 115200 baud
+    pub fn slice(&self, batch_size: u32, millis: u32) -> u32 {
+        let nonces = match self.hashrate() {
+            0 => WARMUP_NONCES as u64,
+            hashrate => hashrate * millis as u64 / 1000,
+        };
+        nonces.min(batch_size as u64).max(1) as u32
+    }
+    
+    /// Zeros of the best hash found so far
+    pub fn best_zeros(&self) -> u32 {
+        self.best.map_or(0, |best| best.zeros)
+    }
+    
+    /// Expected seconds to find a hash meeting the target at the measured hashrate
+    pub fn eta_secs(&self) -> Option<u64> {
+        let hashrate = self.hashrate();
+        if hashrate == 0 {
+            return None;
+        }
+        let expected = ZeroChecker::expected_hashes(self.zeros, self.zero_unit);
+        Some(u64::try_from(expected / hashrate as u128).unwrap_or(u64::MAX))
+    }
+}
+
+/// Result of a single `Miner::step` call
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepOutcome {
+    /// No job in progress
+    Idle,
+    /// Job paused, nothing hashed
+    Paused,
+    /// Batch tested, job still running
+    Running,
+    /// New best hash in best-so-far mode, job still running
+    Improved(MiningResult),
+    /// Job ended
+    Finished(MiningOutcome),
+}
+
+/// Best hash of a mining run - the nonce, its hash and the leading zeros it has
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MiningResult {
+    /// Block the nonce belongs to - lets the host drop stale results
+    pub block_index: u32,
+    pub nonce: u64,
+    pub hash: [u8; 32],
+    pub zeros: u32,
+}
+
+/// How a mining run ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MiningOutcome {
+    /// A nonce meeting the target was found
+    Solved(MiningResult),
+    /// Best-so-far budget spent - best hash found within it
+    Best(MiningResult),
+    /// Whole nonce range tested - best hash found in it
+    Exhausted(MiningResult),
+}
+
 /// Mining errors
 #[derive(Debug, PartialEq)]
 pub enum MiningError {
     NotConfigured,
-    Overflow,
+    InvalidRange,
+    NotRunning,
+    NotPaused,
+    /// The last job was stopped - pause, resume and stop say so until a new job starts
+    Aborted,
+    InfeasibleDifficulty,
+}
+
+impl MiningError {
+    /// Converte o erro para string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MiningError::NotConfigured => "Configure zeros, entropy e farmer primeiro",
+            MiningError::InvalidRange => "Faixa de nonces inválida",
+            MiningError::NotRunning => "Nenhuma mineração em andamento",
+            MiningError::NotPaused => "Mineração não está pausada",
+            MiningError::Aborted => "Mineração abortada",
+            MiningError::InfeasibleDifficulty => "Dificuldade maior que o digest de 256 bits",
+        }
+    }
 }
 
 /// Factory para criar mineradores - facilita testes e extensibilidade
@@ -236,14 +744,75 @@ impl MinerType {
         }
     }
     
-    /// Executes mining
-    pub fn mine<W: Write, L>(&mut self, uart: &mut W, led: &mut L) -> Result<u32, MiningError>
-    where
-        L: embedded_hal::digital::StatefulOutputPin,
-    {
+    /// Starts a job, returning the one it replaced
+    pub fn start(&mut self, budget: Option<MiningBudget>) -> Result<Option<MiningJob>, MiningError> {
+        match self {
+            MinerType::Sha256(miner) => miner.start(budget),
+            MinerType::Keccak256(miner) => miner.start(budget),
+        }
+    }
+    
+    /// Tests up to `batch_size` nonces of the running job
+    pub fn step(&mut self, batch_size: u32) -> StepOutcome {
+        match self {
+            MinerType::Sha256(miner) => miner.step(batch_size),
+            MinerType::Keccak256(miner) => miner.step(batch_size),
+        }
+    }
+    
+    /// Pauses the running job
+    pub fn pause(&mut self) -> Result<(), MiningError> {
+        match self {
+            MinerType::Sha256(miner) => miner.pause(),
+            MinerType::Keccak256(miner) => miner.pause(),
+        }
+    }
+    
+    /// Resumes a paused job
+    pub fn resume(&mut self) -> Result<(), MiningError> {
+        match self {
+            MinerType::Sha256(miner) => miner.resume(),
+            MinerType::Keccak256(miner) => miner.resume(),
+        }
+    }
+    
+    /// Aborts the job and returns the work done so far
+    pub fn stop(&mut self) -> Result<MiningJob, MiningError> {
+        match self {
+            MinerType::Sha256(miner) => miner.stop(),
+            MinerType::Keccak256(miner) => miner.stop(),
+        }
+    }
+    
+    /// Checks if a job is in progress and not paused
+    pub fn is_running(&self) -> bool {
+        match self {
+            MinerType::Sha256(miner) => miner.is_running(),
+            MinerType::Keccak256(miner) => miner.is_running(),
+        }
+    }
+    
+    /// Gets the progress of the current job, running or paused
+    pub fn job(&self) -> Option<&MiningJob> {
+        match self {
+            MinerType::Sha256(miner) => miner.job(),
+            MinerType::Keccak256(miner) => miner.job(),
+        }
+    }
+    
+    /// Recomputes one nonce over the given job parameters
+    pub fn verify(&self, state: &MiningState, nonce: u64) -> MiningResult {
+        match self {
+            MinerType::Sha256(miner) => miner.verify(state, nonce),
+            MinerType::Keccak256(miner) => miner.verify(state, nonce),
+        }
+    }
+    
+    /// Measures the hashrate over `nonces` hashes without touching the job
+    pub fn measure_hashrate(&self, nonces: u32) -> u64 {
         match self {
-            MinerType::Sha256(miner) => miner.mine(uart, led),
-            MinerType::Keccak256(miner) => miner.mine(uart, led),
+            MinerType::Sha256(miner) => miner.measure_hashrate(nonces),
+            MinerType::Keccak256(miner) => miner.measure_hashrate(nonces),
         }
     }
 }
@@ -0,0 +1,40 @@
+//! Module for bench command messages
+
+
+use core::fmt::{Display, Write};
+
+use super::HashAlgorithm;
+use crate::mining::Benchmark;
+
+/// Nonces hashed per algorithm when `bench` gets no count
+pub const DEFAULT_BENCH_NONCES: u32 = 10_000;
+
+/// Largest `bench` count - the benchmark blocks the console while it runs
+pub const MAX_BENCH_NONCES: u32 = 100_000;
+
+/// Sends the benchmark result of one algorithm
+pub fn send_bench_message<W>(uart: &mut W, algorithm: HashAlgorithm, bench: &Benchmark) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    let nanos = bench.nanos_per_hash();
+    write!(
+        uart,
+        "BENCH: algo={}, nonces={}, tempo={}ms, hashrate={} H/s, us_por_hash={}.{:03}\r\n",
+        algorithm.as_str(),
+        bench.nonces,
+        bench.elapsed.as_millis(),
+        bench.hashrate(),
+        nanos / 1000,
+        nanos % 1000
+    )
+}
+
+/// Sends error message for invalid bench command
+pub fn send_bench_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+    E: Display,
+{
+    write!(uart, "BENCH_ERROR: {}\r\n", error)
+}
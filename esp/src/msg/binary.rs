@@ -0,0 +1,338 @@
+//! Binary protocol - commands decoded from frames, events encoded into them
+//!
+//! Each command frame carries the same arguments as its text form, postcard
+//! encoded in order (see `frame::kind` for the types). The farmer travels as
+//! the raw 32-byte key instead of a strkey. Event payloads use the same
+//! layout; units, algorithms and modes travel as their variant index.
+
+use core::fmt::Write;
+
+use super::{
+    Command, ErrorSource, Event, HashAlgorithm, MiningBudget, OutputMode, ParseError, ZeroUnit, FIRMWARE_VERSION,
+    MAX_BENCH_NONCES, MAX_DIGEST_INPUT, MAX_LINE_LEN, PROTOCOL_VERSION,
+};
+use crate::frame::{self, kind, Frame, MAX_ENCODED, MAX_PAYLOAD};
+use crate::mining::{JobParams, MiningJob, MiningResult, ZeroChecker};
+use crate::wire::{Decoder, Encoder, WireError};
+
+impl Command {
+    /// Decodes a command frame, with the same validation as `from_str`
+    pub fn from_frame(frame: &Frame) -> Result<Self, ParseError> {
+        let mut dec = Decoder::new(&frame.payload);
+        let command = match frame.kind {
+            kind::HELP => Command::Help,
+            kind::INFO => Command::Info,
+            kind::RESET => Command::Reset,
+            kind::ZEROS => {
+                let zeros = dec.u8()?;
+                let unit = if dec.option()? { Some(unit_from_index(dec.variant()?)?) } else { None };
+                if unit.is_some_and(|unit| !ZeroChecker::is_feasible(zeros, unit)) {
+                    return Err(ParseError::OutOfRange("zeros"));
+                }
+                Command::Zeros(zeros, unit)
+            }
+            kind::ENTROPY => Command::Entropy(dec.array()?),
+            kind::FARMER => Command::Farmer(dec.array()?),
+            kind::BLOCK => Command::Block(dec.u32()?),
+            kind::START_MINING => {
+                let job = JobParams {
+                    block_index: dec.u32()?,
+                    entropy: dec.array()?,
+                    farmer: dec.array()?,
+                    zeros: dec.u8()?,
+                    batch_size: dec.u32()?,
+                };
+                Command::StartMining(Command::validate_job(job)?)
+            }
+            kind::MINE => {
+                let budget = if dec.option()? {
+                    let budget = match dec.variant()? {
+                        0 => MiningBudget::Nonces(dec.u64()?),
+                        1 => MiningBudget::Millis(dec.u32()?),
+                        tag => return Err(WireError::InvalidTag(tag).into()),
+                    };
+                    if matches!(budget, MiningBudget::Nonces(0) | MiningBudget::Millis(0)) {
+                        return Err(ParseError::OutOfRange("budget"));
+                    }
+                    Some(budget)
+                } else {
+                    None
+                };
+                Command::Mine(budget)
+            }
+            kind::ESTIMATE => Command::Estimate,
+            kind::VERIFY => Command::Verify(dec.u64()?),
+            kind::RANGE => {
+                let start = dec.u64()?;
+                let end = dec.u64()?;
+                if start > end {
+                    return Err(ParseError::OutOfRange("end"));
+                }
+                Command::Range(start, end)
+            }
+            kind::PROGRESS => Command::Progress(dec.u32()?),
+            kind::STOP => Command::Stop,
+            kind::PAUSE => Command::Pause,
+            kind::RESUME => Command::Resume,
+            kind::HASH => Command::Hash(algorithm_from_index(dec.variant()?)?),
+            kind::HASH_INFO => Command::HashInfo,
+            kind::DIGEST => {
+                let algorithm = algorithm_from_index(dec.variant()?)?;
+                let data = dec.bytes()?;
+                if data.len() > MAX_DIGEST_INPUT {
+                    return Err(ParseError::OutOfRange("data"));
+                }
+                let data = heapless::Vec::from_slice(data).map_err(|_| ParseError::OutOfRange("data"))?;
+                Command::Digest(algorithm, data)
+            }
+            kind::SELFTEST => Command::SelfTest,
+            kind::BENCH => {
+                let algorithm = if dec.option()? { Some(algorithm_from_index(dec.variant()?)?) } else { None };
+                let nonces = dec.u32()?;
+                if nonces == 0 || nonces > MAX_BENCH_NONCES {
+                    return Err(ParseError::OutOfRange("n"));
+                }
+                Command::Bench(algorithm, nonces)
+            }
+            kind::MODE => Command::Mode(mode_from_index(dec.variant()?)?),
+            kind::HELLO => Command::Hello,
+            _ => return Err(ParseError::UnknownCommand),
+        };
+        dec.finish()?;
+        Ok(command)
+    }
+}
+
+impl Event<'_> {
+    /// Encodes the event as a complete frame, returning the number of bytes to send
+    pub fn to_frame(&self, seq: u8, out: &mut [u8; MAX_ENCODED]) -> Result<usize, WireError> {
+        let mut payload = [0u8; MAX_PAYLOAD];
+        let mut enc = Encoder::new(&mut payload);
+        self.encode_payload(&mut enc)?;
+        frame::encode(self.kind(), seq, enc.written(), out).map_err(|_| WireError::BufferFull)
+    }
+
+    /// Writes the postcard payload of the event
+    fn encode_payload(&self, enc: &mut Encoder) -> Result<(), WireError> {
+        match self {
+            Event::Ready => Ok(()),
+            Event::Hello => {
+                enc.u32(PROTOCOL_VERSION)?;
+                enc.str(FIRMWARE_VERSION)?;
+                enc.u32(HashAlgorithm::ALL.len() as u32)?;
+                for algorithm in HashAlgorithm::ALL {
+                    enc.variant(algorithm_index(algorithm))?;
+                }
+                enc.u32(ZeroUnit::ALL.len() as u32)?;
+                for unit in ZeroUnit::ALL {
+                    enc.variant(unit_index(unit))?;
+                }
+                enc.u32(MAX_LINE_LEN as u32)?;
+                enc.u32(MAX_PAYLOAD as u32)?;
+                enc.u32(OutputMode::ALL.len() as u32)?;
+                for mode in OutputMode::ALL {
+                    enc.variant(mode_index(mode))?;
+                }
+                Ok(())
+            }
+            Event::Help => {
+                // Supported command frame types
+                enc.bytes(&kind::COMMANDS)
+            }
+            Event::Info { state, job } => {
+                enc.variant(algorithm_index(state.hash_algorithm))?;
+                enc.u8(state.zeros)?;
+                enc.variant(unit_index(state.zero_unit))?;
+                enc.u32(state.block_index)?;
+                enc.raw(&state.entropy)?;
+                enc.raw(&state.farmer)?;
+                enc.u64(state.nonce_start)?;
+                enc.u64(state.nonce_end)?;
+                enc.u32(state.progress_interval_ms)?;
+                enc.u32(state.batch_size)?;
+                enc.option(state.last_nonce.is_some())?;
+                if let Some(nonce) = state.last_nonce {
+                    enc.u64(nonce)?;
+                }
+                // 0 parada, 1 em andamento, 2 pausada
+                let status = match job {
+                    None => 0,
+                    Some(job) if job.paused_at.is_none() => 1,
+                    Some(_) => 2,
+                };
+                enc.variant(status)
+            }
+            Event::Reset { state } => {
+                enc.u8(state.zeros)?;
+                enc.variant(unit_index(state.zero_unit))?;
+                enc.raw(&state.entropy)?;
+                enc.bool(state.is_configured)
+            }
+            Event::Zeros { zeros, unit } => {
+                enc.u8(*zeros)?;
+                enc.variant(unit_index(*unit))
+            }
+            Event::Entropy(bytes) | Event::Farmer(bytes) => enc.raw(*bytes),
+            Event::Block(block_index) => enc.u32(*block_index),
+            Event::Range { start, end } => {
+                enc.u64(*start)?;
+                enc.u64(*end)
+            }
+            Event::ProgressInterval(interval_ms) => enc.u32(*interval_ms),
+            Event::Hash(algorithm) | Event::HashInfo(algorithm) => {
+                enc.variant(algorithm_index(*algorithm))
+            }
+            Event::Mode(mode) => enc.variant(mode_index(*mode)),
+            Event::MineStart { block_index, zeros, unit, entropy } => {
+                enc.u32(*block_index)?;
+                enc.u8(*zeros)?;
+                enc.variant(unit_index(*unit))?;
+                enc.raw(*entropy)
+            }
+            Event::Found { result, unit, algorithm }
+            | Event::MineResult { result, unit, algorithm } => {
+                encode_result(enc, result, *unit)?;
+                enc.variant(algorithm_index(*algorithm))
+            }
+            Event::MineBest { result, unit } => encode_result(enc, result, *unit),
+            Event::MineExhausted { start, end, best, unit } => {
+                enc.u64(*start)?;
+                enc.u64(*end)?;
+                encode_result(enc, best, *unit)
+            }
+            Event::MineStopped(job)
+            | Event::MinePaused(job)
+            | Event::MineResumed(job)
+            | Event::MineProgress(job) => encode_job(enc, job),
+            Event::Estimate { zeros, unit, hashrate } => {
+                enc.u8(*zeros)?;
+                enc.variant(unit_index(*unit))?;
+                enc.u128(ZeroChecker::expected_hashes(*zeros, *unit))?;
+                enc.u64(*hashrate)
+            }
+            Event::Verify { result, target, unit, algorithm } => {
+                enc.bool(result.zeros >= *target as u32)?;
+                enc.u8(*target)?;
+                encode_result(enc, result, *unit)?;
+                enc.variant(algorithm_index(*algorithm))
+            }
+            Event::Digest { algorithm, len, hash } => {
+                enc.variant(algorithm_index(*algorithm))?;
+                enc.u32(*len as u32)?;
+                enc.raw(*hash)
+            }
+            Event::Bench { algorithm, bench } => {
+                enc.variant(algorithm_index(*algorithm))?;
+                enc.u32(bench.nonces)?;
+                enc.u64(bench.elapsed.as_micros())?;
+                enc.u64(bench.hashrate())
+            }
+            Event::SelfTestCase { name, ok } => {
+                enc.str(name)?;
+                enc.bool(*ok)
+            }
+            Event::SelfTestDone(summary) => {
+                enc.u32(summary.passed)?;
+                enc.u32(summary.total)
+            }
+            Event::Error { source, message } => {
+                let mut text: heapless::String<96> = heapless::String::new();
+                // A message longer than the buffer is sent truncated
+                let _ = write!(text, "{}", message);
+                enc.variant(*source as u32)?;
+                enc.str(&text)
+            }
+            Event::Unknown(cmd) => {
+                // Same layout as an error from `ErrorSource::Command`
+                let end = cmd.char_indices().nth(96).map_or(cmd.len(), |(i, _)| i);
+                enc.variant(ErrorSource::Command as u32)?;
+                enc.str(&cmd[..end])
+            }
+            Event::Nak(error) => enc.u8(error.code()),
+        }
+    }
+}
+
+impl From<WireError> for ParseError {
+    fn from(error: WireError) -> Self {
+        ParseError::InvalidPayload(error)
+    }
+}
+
+/// Block, nonce, digest and zero count of a result
+fn encode_result(enc: &mut Encoder, result: &MiningResult, unit: ZeroUnit) -> Result<(), WireError> {
+    enc.u32(result.block_index)?;
+    enc.u64(result.nonce)?;
+    enc.raw(&result.hash)?;
+    enc.u32(result.zeros)?;
+    enc.variant(unit_index(unit))
+}
+
+/// Progress of a job
+fn encode_job(enc: &mut Encoder, job: &MiningJob) -> Result<(), WireError> {
+    enc.u32(job.block_index)?;
+    enc.u64(job.tested)?;
+    enc.u64(job.next_nonce)?;
+    enc.u32(job.best_zeros())?;
+    enc.variant(unit_index(job.zero_unit))?;
+    enc.u64(job.hashrate())?;
+    enc.u64(job.elapsed().as_millis())?;
+    let eta = job.eta_secs();
+    enc.option(eta.is_some())?;
+    if let Some(eta) = eta {
+        enc.u64(eta)?;
+    }
+    Ok(())
+}
+
+/// Variant index of a zero unit on the wire
+pub fn unit_index(unit: ZeroUnit) -> u32 {
+    match unit {
+        ZeroUnit::Bits => 0,
+        ZeroUnit::Nibbles => 1,
+        ZeroUnit::Bytes => 2,
+    }
+}
+
+fn unit_from_index(index: u32) -> Result<ZeroUnit, ParseError> {
+    match index {
+        0 => Ok(ZeroUnit::Bits),
+        1 => Ok(ZeroUnit::Nibbles),
+        2 => Ok(ZeroUnit::Bytes),
+        _ => Err(ParseError::UnsupportedUnit),
+    }
+}
+
+/// Variant index of a hash algorithm on the wire
+pub fn algorithm_index(algorithm: HashAlgorithm) -> u32 {
+    match algorithm {
+        HashAlgorithm::Sha256 => 0,
+        HashAlgorithm::Keccak256 => 1,
+    }
+}
+
+fn algorithm_from_index(index: u32) -> Result<HashAlgorithm, ParseError> {
+    match index {
+        0 => Ok(HashAlgorithm::Sha256),
+        1 => Ok(HashAlgorithm::Keccak256),
+        _ => Err(ParseError::UnsupportedAlgorithm),
+    }
+}
+
+/// Variant index of an output mode on the wire
+pub fn mode_index(mode: OutputMode) -> u32 {
+    match mode {
+        OutputMode::Text => 0,
+        OutputMode::Binary => 1,
+        OutputMode::Json => 2,
+    }
+}
+
+fn mode_from_index(index: u32) -> Result<OutputMode, ParseError> {
+    match index {
+        0 => Ok(OutputMode::Text),
+        1 => Ok(OutputMode::Binary),
+        2 => Ok(OutputMode::Json),
+        _ => Err(ParseError::UnsupportedMode),
+    }
+}
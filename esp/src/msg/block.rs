@@ -0,0 +1,21 @@
+//! Module for block index command messages
+
+
+use core::fmt::{Display, Write};
+
+/// Sends confirmation message for block command
+pub fn send_block_message<W>(uart: &mut W, block_index: u32) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "BLOCK: bloco {} configurado para mineração\r\n", block_index)
+}
+
+/// Sends error message for invalid block command
+pub fn send_block_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+    E: Display,
+{
+    write!(uart, "BLOCK_ERROR: {}\r\n", error)
+}
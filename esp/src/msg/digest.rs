@@ -0,0 +1,30 @@
+//! Module for digest command messages
+
+
+use core::fmt::{Display, Write};
+
+use super::{HashAlgorithm, MAX_LINE_LEN};
+use crate::hex::Hex;
+
+/// Longest console line before the data: biggest `#<id>` and algorithm name
+const DIGEST_LINE_PREFIX: &str = "#4294967295 digest keccak256 0x";
+
+/// Most bytes `digest` accepts - what fits a console line as hex after the longest prefix
+pub const MAX_DIGEST_INPUT: usize = (MAX_LINE_LEN - DIGEST_LINE_PREFIX.len()) / 2;
+
+/// Sends the digest of arbitrary input bytes
+pub fn send_digest_message<W>(uart: &mut W, algorithm: HashAlgorithm, len: usize, hash: &[u8; 32]) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "DIGEST: algo={}, len={}, hash={}\r\n", algorithm.as_str(), len, Hex(hash))
+}
+
+/// Sends error message for invalid digest command
+pub fn send_digest_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+    E: Display,
+{
+    write!(uart, "DIGEST_ERROR: {}\r\n", error)
+}
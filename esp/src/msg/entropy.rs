@@ -1,20 +1,23 @@
 //! Module for entropy command messages
 
 
-use core::fmt::Write;
+use core::fmt::{Display, Write};
+
+use crate::hex::Hex;
 
 /// Sends confirmation message for entropy command
-pub fn send_entropy_message<W>(uart: &mut W, entropy: u8) -> Result<(), core::fmt::Error>
+pub fn send_entropy_message<W>(uart: &mut W, entropy: &[u8; 32]) -> Result<(), core::fmt::Error>
 where
     W: Write,
 {
-    write!(uart, "ENTROPY: {} configurado para mineração\r\n", entropy)
+    write!(uart, "ENTROPY: {} configurado para mineração\r\n", Hex(entropy))
 }
 
 /// Sends error message for invalid entropy command
-pub fn send_entropy_error_message<W>(uart: &mut W, error: &str) -> Result<(), core::fmt::Error>
+pub fn send_entropy_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
 where
     W: Write,
+    E: Display,
 {
     write!(uart, "ENTROPY_ERROR: {}\r\n", error)
 }
\ No newline at end of file
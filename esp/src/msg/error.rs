@@ -0,0 +1,68 @@
+//! Module for command parse errors
+//!
+//! Each error is reported with the `*_ERROR` prefix of the command it came
+//! from - see `ErrorSource` and the text encoder.
+
+use core::fmt::{self, Display, Write};
+
+use crate::hex::HexError;
+use crate::strkey::StrkeyError;
+use crate::wire::WireError;
+
+/// Why a command line could not be parsed - arguments are named by the `&str`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParseError {
+    /// First word is not a known command
+    UnknownCommand,
+    /// More tokens than any command takes
+    TooManyArguments,
+    /// Required argument not given
+    MissingArgument(&'static str),
+    /// Argument is not a decimal number
+    InvalidNumber(&'static str),
+    /// Number does not fit the argument's range
+    OutOfRange(&'static str),
+    /// Hash algorithm other than sha256/keccak256
+    UnsupportedAlgorithm,
+    /// Zero unit other than bits/nibbles/bytes
+    UnsupportedUnit,
+    /// Console mode other than text/json/binary
+    UnsupportedMode,
+    /// Malformed hex argument
+    InvalidHex(&'static str, HexError),
+    /// Malformed Stellar address
+    InvalidStrkey(StrkeyError),
+    /// Malformed binary frame payload
+    InvalidPayload(WireError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnknownCommand => write!(f, "comando desconhecido"),
+            ParseError::TooManyArguments => write!(f, "argumentos demais"),
+            ParseError::MissingArgument(name) => write!(f, "argumento '{}' ausente", name),
+            ParseError::InvalidNumber(name) => write!(f, "'{}' não é um número válido", name),
+            ParseError::OutOfRange(name) => write!(f, "'{}' fora do intervalo permitido", name),
+            ParseError::UnsupportedAlgorithm => {
+                write!(f, "algoritmo não suportado (use sha256 ou keccak256)")
+            }
+            ParseError::UnsupportedUnit => {
+                write!(f, "unidade não suportada (use bits, nibbles ou bytes)")
+            }
+            ParseError::UnsupportedMode => write!(f, "modo não suportado (use text, json ou binary)"),
+            ParseError::InvalidHex(name, error) => write!(f, "{}: {}", name, error),
+            ParseError::InvalidStrkey(error) => write!(f, "{}", error),
+            ParseError::InvalidPayload(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Sends an error that belongs to no command (e.g. a rejected frame)
+pub fn send_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+    E: Display,
+{
+    write!(uart, "ERROR: {}\r\n", error)
+}
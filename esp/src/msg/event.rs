@@ -0,0 +1,259 @@
+//! Device events - typed content of every output
+//!
+//! Everything the device sends is an `Event`; `Event::emit` picks the
+//! encoder for the console mode in one place. The encoders live next to
+//! their protocol: `text` (the `send_*` lines), `json` and `binary`.
+
+use core::fmt::{self, Display, Write};
+
+#[cfg(feature = "esp")]
+use esp_hal::{uart::Uart, DriverMode};
+
+use super::text::Tagged;
+use super::{HashAlgorithm, OutputMode, ParseError, ZeroUnit};
+use crate::frame::{kind, FrameError, MAX_ENCODED};
+use crate::mining::{Benchmark, MiningJob, MiningResult, MiningState};
+use crate::selftest::SelfTestSummary;
+
+/// Command an error refers to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorSource {
+    Command,
+    Zeros,
+    Entropy,
+    Farmer,
+    Block,
+    Hash,
+    Mine,
+    StartMining,
+    Digest,
+    Bench,
+    Mode,
+}
+
+impl ErrorSource {
+    /// Source of a failed binary command, from its frame type
+    pub fn from_kind(command: u8) -> Self {
+        match command {
+            kind::ZEROS => ErrorSource::Zeros,
+            kind::ENTROPY => ErrorSource::Entropy,
+            kind::FARMER => ErrorSource::Farmer,
+            kind::BLOCK => ErrorSource::Block,
+            kind::HASH => ErrorSource::Hash,
+            kind::START_MINING => ErrorSource::StartMining,
+            kind::MINE | kind::RANGE | kind::PROGRESS | kind::VERIFY | kind::STOP
+            | kind::PAUSE | kind::RESUME => ErrorSource::Mine,
+            kind::DIGEST => ErrorSource::Digest,
+            kind::BENCH => ErrorSource::Bench,
+            kind::MODE => ErrorSource::Mode,
+            _ => ErrorSource::Command,
+        }
+    }
+
+    /// Source of a failed text command, from its first word
+    pub fn from_command(cmd: &str) -> Self {
+        let command = cmd.split_whitespace().next().unwrap_or("");
+        let is = |name: &str| command.eq_ignore_ascii_case(name);
+        if is("zeros") {
+            ErrorSource::Zeros
+        } else if is("entropy") {
+            ErrorSource::Entropy
+        } else if is("farmer") {
+            ErrorSource::Farmer
+        } else if is("block") {
+            ErrorSource::Block
+        } else if is("hash") {
+            ErrorSource::Hash
+        } else if is("start_mining") {
+            ErrorSource::StartMining
+        } else if is("mine") || is("range") || is("progress") || is("verify") {
+            ErrorSource::Mine
+        } else if is("digest") {
+            ErrorSource::Digest
+        } else if is("bench") {
+            ErrorSource::Bench
+        } else if is("mode") {
+            ErrorSource::Mode
+        } else {
+            ErrorSource::Command
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorSource::Command => "command",
+            ErrorSource::Zeros => "zeros",
+            ErrorSource::Entropy => "entropy",
+            ErrorSource::Farmer => "farmer",
+            ErrorSource::Block => "block",
+            ErrorSource::Hash => "hash",
+            ErrorSource::Mine => "mine",
+            ErrorSource::StartMining => "start_mining",
+            ErrorSource::Digest => "digest",
+            ErrorSource::Bench => "bench",
+            ErrorSource::Mode => "mode",
+        }
+    }
+}
+
+/// Everything the device reports
+pub enum Event<'a> {
+    /// Boot banner
+    Ready,
+    /// Protocol version and capabilities
+    Hello,
+    Help,
+    Info { state: &'a MiningState, job: Option<&'a MiningJob> },
+    Reset { state: &'a MiningState },
+    Zeros { zeros: u8, unit: ZeroUnit },
+    Entropy(&'a [u8; 32]),
+    Farmer(&'a [u8; 32]),
+    Block(u32),
+    Range { start: u64, end: u64 },
+    ProgressInterval(u32),
+    Hash(HashAlgorithm),
+    HashInfo(HashAlgorithm),
+    Mode(OutputMode),
+    MineStart { block_index: u32, zeros: u8, unit: ZeroUnit, entropy: &'a [u8; 32] },
+    Found { result: &'a MiningResult, unit: ZeroUnit, algorithm: HashAlgorithm },
+    MineResult { result: &'a MiningResult, unit: ZeroUnit, algorithm: HashAlgorithm },
+    MineBest { result: &'a MiningResult, unit: ZeroUnit },
+    MineExhausted { start: u64, end: u64, best: &'a MiningResult, unit: ZeroUnit },
+    MineStopped(&'a MiningJob),
+    MinePaused(&'a MiningJob),
+    MineResumed(&'a MiningJob),
+    MineProgress(&'a MiningJob),
+    Estimate { zeros: u8, unit: ZeroUnit, hashrate: u64 },
+    Verify { result: &'a MiningResult, target: u8, unit: ZeroUnit, algorithm: HashAlgorithm },
+    Digest { algorithm: HashAlgorithm, len: usize, hash: &'a [u8; 32] },
+    Bench { algorithm: HashAlgorithm, bench: &'a Benchmark },
+    SelfTestCase { name: &'a str, ok: bool },
+    SelfTestDone(SelfTestSummary),
+    Error { source: ErrorSource, message: &'a dyn Display },
+    /// Text line that is not a command
+    Unknown(&'a str),
+    /// Corrupt frame, binary mode only
+    Nak(FrameError),
+}
+
+impl<'a> Event<'a> {
+    /// Error reply for a command line that failed to parse
+    pub fn parse_error(cmd: &'a str, error: &'a ParseError) -> Self {
+        match ErrorSource::from_command(cmd) {
+            ErrorSource::Command => Event::Unknown(cmd),
+            source => Event::Error { source, message: error },
+        }
+    }
+
+    /// Writes the event in the given mode, tagged with the id of its request
+    ///
+    /// The id prefixes every text line as `#<id>`, is the `id` field in JSON
+    /// and the seq of a frame (binary requests are identified by their seq).
+    pub fn emit<S: Sink>(&self, out: &mut S, mode: OutputMode, id: Option<u32>) -> fmt::Result {
+        match mode {
+            OutputMode::Text => match id {
+                Some(id) => self.write_text(&mut Tagged::new(out, id)),
+                None => self.write_text(out),
+            },
+            OutputMode::Json => self.write_json(out, id),
+            OutputMode::Binary => {
+                let mut frame = [0u8; MAX_ENCODED];
+                let seq = id.map_or(0, |id| id as u8);
+                let len = self.to_frame(seq, &mut frame).map_err(|_| fmt::Error)?;
+                out.write_bytes(&frame[..len])
+            }
+        }
+    }
+
+    /// Frame type of the event
+    pub fn kind(&self) -> u8 {
+        match self {
+            Event::Ready => kind::READY,
+            Event::Hello => kind::HELLO_REPLY,
+            Event::Help => kind::HELP_REPLY,
+            Event::Info { .. } => kind::INFO_REPLY,
+            Event::Reset { .. } => kind::RESET_REPLY,
+            Event::Zeros { .. } => kind::ZEROS_SET,
+            Event::Entropy(_) => kind::ENTROPY_SET,
+            Event::Farmer(_) => kind::FARMER_SET,
+            Event::Block(_) => kind::BLOCK_SET,
+            Event::Range { .. } => kind::RANGE_SET,
+            Event::ProgressInterval(_) => kind::PROGRESS_SET,
+            Event::Hash(_) => kind::HASH_SET,
+            Event::HashInfo(_) => kind::HASH_CURRENT,
+            Event::Mode(_) => kind::MODE_SET,
+            Event::MineStart { .. } => kind::MINE_START,
+            Event::Found { .. } => kind::FOUND,
+            Event::MineResult { .. } => kind::MINE_RESULT,
+            Event::MineBest { .. } => kind::MINE_BEST,
+            Event::MineExhausted { .. } => kind::MINE_EXHAUSTED,
+            Event::MineStopped(_) => kind::MINE_STOPPED,
+            Event::MinePaused(_) => kind::MINE_PAUSED,
+            Event::MineResumed(_) => kind::MINE_RESUMED,
+            Event::MineProgress(_) => kind::MINE_PROGRESS,
+            Event::Estimate { .. } => kind::ESTIMATE_REPLY,
+            Event::Verify { .. } => kind::VERIFY_REPLY,
+            Event::Digest { .. } => kind::DIGEST_REPLY,
+            Event::Bench { .. } => kind::BENCH_REPLY,
+            Event::SelfTestCase { .. } => kind::SELFTEST_CASE,
+            Event::SelfTestDone(_) => kind::SELFTEST_DONE,
+            Event::Error { .. } | Event::Unknown(_) => kind::ERROR,
+            Event::Nak(_) => kind::NAK,
+        }
+    }
+
+    /// Value of the `type` field in JSON
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::Ready => "ready",
+            Event::Hello => "hello",
+            Event::Help => "help",
+            Event::Info { .. } => "info",
+            Event::Reset { .. } => "reset",
+            Event::Zeros { .. } => "zeros",
+            Event::Entropy(_) => "entropy",
+            Event::Farmer(_) => "farmer",
+            Event::Block(_) => "block",
+            Event::Range { .. } => "range",
+            Event::ProgressInterval(_) => "progress_interval",
+            Event::Hash(_) => "hash",
+            Event::HashInfo(_) => "hash_info",
+            Event::Mode(_) => "mode",
+            Event::MineStart { .. } => "mine_start",
+            Event::Found { .. } => "found",
+            Event::MineResult { .. } => "mine_result",
+            Event::MineBest { .. } => "mine_best",
+            Event::MineExhausted { .. } => "mine_exhausted",
+            Event::MineStopped(_) => "mine_stopped",
+            Event::MinePaused(_) => "mine_paused",
+            Event::MineResumed(_) => "mine_resumed",
+            Event::MineProgress(_) => "mine_progress",
+            Event::Estimate { .. } => "estimate",
+            Event::Verify { .. } => "verify",
+            Event::Digest { .. } => "digest",
+            Event::Bench { .. } => "bench",
+            Event::SelfTestCase { .. } => "selftest",
+            Event::SelfTestDone(_) => "selftest_done",
+            Event::Error { .. } => "error",
+            Event::Unknown(_) => "unknown_command",
+            Event::Nak(_) => "nak",
+        }
+    }
+}
+
+/// Where events go - text and JSON through `Write`, frames through `write_bytes`
+pub trait Sink: Write {
+    fn write_bytes(&mut self, bytes: &[u8]) -> fmt::Result;
+}
+
+#[cfg(feature = "esp")]
+impl<D: DriverMode> Sink for Uart<D> {
+    /// Retries until the TX FIFO took every byte
+    fn write_bytes(&mut self, mut bytes: &[u8]) -> fmt::Result {
+        while !bytes.is_empty() {
+            let written = self.write(bytes).map_err(|_| fmt::Error)?;
+            bytes = &bytes[written..];
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+//! Module for farmer address command messages
+
+
+use core::fmt::{Display, Write};
+
+use crate::strkey::AccountId;
+
+/// Sends confirmation message for farmer command
+pub fn send_farmer_message<W>(uart: &mut W, farmer: &[u8; 32]) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "FARMER: {} configurado para mineração\r\n", AccountId(farmer))
+}
+
+/// Sends error message for invalid farmer command
+pub fn send_farmer_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+    E: Display,
+{
+    write!(uart, "FARMER_ERROR: {}\r\n", error)
+}
@@ -1,6 +1,6 @@
 //! Module for hash algorithm selection command
 
-use core::fmt::Write;
+use core::fmt::{Display, Write};
 
 /// Enum to represent available hash algorithms
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -10,6 +10,9 @@ pub enum HashAlgorithm {
 }
 
 impl HashAlgorithm {
+    /// Every supported algorithm, in wire order
+    pub const ALL: [HashAlgorithm; 2] = [HashAlgorithm::Sha256, HashAlgorithm::Keccak256];
+
     /// Parse um algoritmo a partir de uma string
     pub fn from_str(s: &str) -> Option<Self> {
         // Manual case-insensitive comparison for no_std
@@ -29,6 +32,14 @@ impl HashAlgorithm {
             HashAlgorithm::Keccak256 => "Keccak-256",
         }
     }
+
+    /// Command-line name, as accepted by `from_str` - stable for machine output
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Keccak256 => "keccak256",
+        }
+    }
 }
 
 /// Sends confirmation message for hash algorithm change
@@ -37,7 +48,7 @@ pub fn send_hash_message<W: Write>(uart: &mut W, algorithm: HashAlgorithm) -> Re
 }
 
 /// Sends error message for invalid hash command
-pub fn send_hash_error_message<W: Write>(uart: &mut W, error: &str) -> Result<(), core::fmt::Error> {
+pub fn send_hash_error_message<W: Write, E: Display>(uart: &mut W, error: E) -> Result<(), core::fmt::Error> {
     write!(uart, "[HASH_ERROR] {}\r\n", error)
 }
 
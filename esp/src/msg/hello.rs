@@ -0,0 +1,47 @@
+//! Module for the protocol handshake
+//!
+//! `hello` tells a host what this firmware speaks, so it can adapt to older
+//! or newer versions without parsing `info` or `help`.
+
+use core::fmt::Write;
+
+use super::{HashAlgorithm, OutputMode, ZeroUnit};
+use crate::frame::MAX_PAYLOAD;
+
+/// Version of the console protocol - bumped on incompatible changes
+pub const PROTOCOL_VERSION: u32 = 1;
+/// Firmware version, from the crate manifest
+pub const FIRMWARE_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Longest command line accepted, in bytes
+pub const MAX_LINE_LEN: usize = 256;
+/// Console baud rate
+pub const BAUD_RATE: u32 = 115_200;
+
+/// Sends the protocol version and the supported algorithms, units and modes
+pub fn send_hello_message<W>(uart: &mut W) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "HELLO: protocolo={}, firmware={}, algos=", PROTOCOL_VERSION, FIRMWARE_VERSION)?;
+    write_list(uart, HashAlgorithm::ALL.iter().map(HashAlgorithm::name))?;
+    write!(uart, ", unidades=")?;
+    write_list(uart, ZeroUnit::ALL.iter().map(ZeroUnit::as_str))?;
+    write!(uart, ", linha_max={}, payload_max={}, modos=", MAX_LINE_LEN, MAX_PAYLOAD)?;
+    write_list(uart, OutputMode::ALL.iter().map(OutputMode::as_str))?;
+    write!(uart, "\r\n")
+}
+
+/// Writes names separated by `|` (commas already split the fields)
+fn write_list<'a, W, I>(uart: &mut W, names: I) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+    I: Iterator<Item = &'a str>,
+{
+    for (i, name) in names.enumerate() {
+        if i > 0 {
+            uart.write_char('|')?;
+        }
+        uart.write_str(name)?;
+    }
+    Ok(())
+}
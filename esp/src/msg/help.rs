@@ -1,26 +1,51 @@
 //! Mensagem de ajuda do sistema
 
-use esp_hal::uart::Uart;
-use esp_hal::DriverMode;
 use core::fmt::Write;
 
+/// Sends the boot banner
+pub fn send_banner_message<W>(uart: &mut W) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "ESP32 Serial Comunicação iniciada!\r\n")?;
+    write!(uart, "Digite 'help' para ver comandos disponíveis\r\n")
+}
+
 /// Sends help message with all available commands
-pub fn send_help_message<D>(uart: &mut Uart<D>) -> Result<(), core::fmt::Error> 
+pub fn send_help_message<W>(uart: &mut W) -> Result<(), core::fmt::Error> 
 where
-    D: DriverMode,
+    W: Write,
 {
     write!(uart, "\r\n=== Comandos Disponíveis ===\r\n")?;
     write!(uart, "help     - Mostra esta ajuda\r\n")?;
+    write!(uart, "hello    - Versão do protocolo e capacidades\r\n")?;
     write!(uart, "info     - Informações do dispositivo\r\n")?;
     write!(uart, "reset    - Reinicia contador\r\n")?;
+    write!(uart, "selftest - Testa os hashers com vetores conhecidos\r\n")?;
+    write!(uart, "#N CMD   - Repete o id N em cada resposta (ex: #7 mine)\r\n")?;
+    write!(uart, "mode json - Uma linha JSON por resposta ('mode text' volta)\r\n")?;
+    write!(uart, "mode binary- Passa para frames COBS com CRC (ver frame.rs)\r\n")?;
     write!(uart, "\r\n=== Comandos de Mineração ===\r\n")?;
-    write!(uart, "zeros N  - Define número de zeros (ex: zeros 8)\r\n")?;
-    write!(uart, "entropy N- Define entropy (ex: entropy 5)\r\n")?;
+    write!(uart, "zeros N [bits|nibbles|bytes] - Define zeros (ex: zeros 8 nibbles)\r\n")?;
+    write!(uart, "entropy HEX- Define entropy, hash de 32 bytes do bloco anterior (64 hex)\r\n")?;
+    write!(uart, "block N  - Define o índice do bloco (ex: block 1234)\r\n")?;
+    write!(uart, "farmer G...- Define o endereço Stellar do farmer\r\n")?;
     write!(uart, "mine     - Inicia mineração\r\n")?;
+    write!(uart, "mine N[ms]- Melhor hash em N nonces ou N ms (ex: mine 5000ms)\r\n")?;
+    write!(uart, "estimate - Hashes esperados e tempo para a dificuldade atual\r\n")?;
+    write!(uart, "verify N - Recalcula o hash do nonce N com a configuração atual\r\n")?;
+    write!(uart, "START_MINING BLOCO ENTROPY FARMER ZEROS LOTE - Configura e inicia o job de uma vez\r\n")?;
+    write!(uart, "range A B- Faixa de nonces A..=B (ex: range 0 999999)\r\n")?;
+    write!(uart, "progress MS- Progresso a cada MS ms, 0 desativa (ex: progress 1000)\r\n")?;
+    write!(uart, "stop     - Aborta a mineração\r\n")?;
+    write!(uart, "pause    - Pausa a mineração\r\n")?;
+    write!(uart, "resume   - Continua do nonce onde parou\r\n")?;
     write!(uart, "\r\n=== Comandos de Hash ===\r\n")?;
     write!(uart, "hash     - Mostra algoritmo atual\r\n")?;
     write!(uart, "hash sha256   - Usa SHA-256\r\n")?;
     write!(uart, "hash keccak256- Usa Keccak-256\r\n")?;
+    write!(uart, "bench [ALGO] [N]- Mede H/s com N nonces (padrão: ambos, 10000)\r\n")?;
+    write!(uart, "digest ALGO HEX- Hash de bytes arbitrários (ex: digest sha256 616263)\r\n")?;
     write!(uart, "============================\r\n\r\n")?;
     Ok(())
 }
\ No newline at end of file
@@ -1,29 +1,40 @@
 //! Device information message
 
-use esp_hal::uart::Uart;
-use esp_hal::DriverMode;
 use core::fmt::Write;
 
-use crate::mining::MiningState;
+use crate::hex::Hex;
+use crate::strkey::AccountId;
+use crate::mining::{MiningJob, MiningState};
+use super::{BAUD_RATE, FIRMWARE_VERSION, PROTOCOL_VERSION};
 
 /// Sends device information and mining state message
-pub fn send_info_message<D>(uart: &mut Uart<D>, mining_state: &MiningState) -> Result<(), core::fmt::Error>
+pub fn send_info_message<W>(uart: &mut W, mining_state: &MiningState, job: Option<&MiningJob>) -> Result<(), core::fmt::Error>
 where
-    D: DriverMode,
+    W: Write,
 {
     writeln!(uart, "=== Informações do ESP32 ===")?;
     writeln!(uart, "Dispositivo: ESP32")?;
-    writeln!(uart, "Firmware: Serial Echo v1.0")?;
-    writeln!(uart, "UART: 115200 baud")?;
+    writeln!(uart, "Firmware: kalesp v{} (protocolo {})", FIRMWARE_VERSION, PROTOCOL_VERSION)?;
+    writeln!(uart, "UART: {} baud", BAUD_RATE)?;
     writeln!(uart, "Pinos: TX=GPIO1, RX=GPIO3")?;
     writeln!(uart, "=== Estado de Mineração ===")?;
     writeln!(uart, "Algoritmo: {}", mining_state.get_hash_algorithm().as_str())?;
-    writeln!(uart, "Zeros: {}", mining_state.zeros)?;
-    writeln!(uart, "Entropy: {}", mining_state.entropy)?;
+    writeln!(uart, "Zeros: {} ({})", mining_state.zeros, mining_state.zero_unit.as_str())?;
+    writeln!(uart, "Bloco: {}", mining_state.block_index)?;
+    writeln!(uart, "Entropy: {}", Hex(&mining_state.entropy))?;
+    writeln!(uart, "Farmer: {}", AccountId(&mining_state.farmer))?;
+    writeln!(uart, "Faixa de nonces: {}-{}", mining_state.nonce_start, mining_state.nonce_end)?;
+    writeln!(uart, "Progresso: {} ms", mining_state.progress_interval_ms)?;
+    writeln!(uart, "Lote: {} nonces", mining_state.batch_size)?;
     match mining_state.last_nonce {
         Some(nonce) => writeln!(uart, "Último nonce: {}", nonce)?,
         None => writeln!(uart, "Último nonce: -1")?,
     }
+    match job {
+        Some(job) if job.paused_at.is_some() => writeln!(uart, "Mineração: pausada (nonce={}, testados={})", job.next_nonce, job.tested)?,
+        Some(job) => writeln!(uart, "Mineração: em andamento (nonce={}, testados={})", job.next_nonce, job.tested)?,
+        None => writeln!(uart, "Mineração: parada")?,
+    }
     writeln!(uart, "============================")?;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,164 @@
+//! JSON encoder for device events
+//!
+//! One compact object per line; `type` is `Event::name`, units, algorithms
+//! and modes travel as their command-line names.
+
+use core::fmt::{self, Write};
+
+use super::{
+    Event, HashAlgorithm, OutputMode, ZeroUnit, COMMAND_NAMES, FIRMWARE_VERSION, MAX_LINE_LEN, PROTOCOL_VERSION,
+};
+use crate::frame::MAX_PAYLOAD;
+use crate::json::JsonObject;
+use crate::mining::{MiningJob, MiningResult, ZeroChecker};
+use crate::strkey::AccountId;
+
+impl Event<'_> {
+    /// Writes the event as one compact JSON object followed by CR/LF
+    pub fn write_json<W: Write>(&self, out: &mut W, id: Option<u32>) -> fmt::Result {
+        let mut json = JsonObject::begin(out, self.name())?;
+        if let Some(id) = id {
+            json.number("id", id)?;
+        }
+        match self {
+            Event::Ready => {}
+            Event::Hello => {
+                json.number("protocol", PROTOCOL_VERSION)?;
+                json.str("firmware", FIRMWARE_VERSION)?;
+                json.str_array("algos", &HashAlgorithm::ALL.map(|algorithm| algorithm.name()))?;
+                json.str_array("units", &ZeroUnit::ALL.map(|unit| unit.as_str()))?;
+                json.number("max_line", MAX_LINE_LEN as u64)?;
+                json.number("max_payload", MAX_PAYLOAD as u64)?;
+                json.str_array("modes", &OutputMode::ALL.map(|mode| mode.as_str()))?;
+            }
+            Event::Help => json.str_array("commands", &COMMAND_NAMES)?,
+            Event::Info { state, job } => {
+                json.str("algo", state.hash_algorithm.name())?;
+                json.number("zeros", state.zeros)?;
+                json.str("unit", state.zero_unit.as_str())?;
+                json.number("block", state.block_index)?;
+                json.hex("entropy", &state.entropy)?;
+                json.display("farmer", AccountId(&state.farmer))?;
+                json.number("nonce_start", state.nonce_start)?;
+                json.number("nonce_end", state.nonce_end)?;
+                json.number("progress_ms", state.progress_interval_ms)?;
+                json.number("batch_size", state.batch_size)?;
+                json.optional("last_nonce", state.last_nonce)?;
+                let status = match job {
+                    None => "idle",
+                    Some(job) if job.paused_at.is_none() => "running",
+                    Some(_) => "paused",
+                };
+                json.str("status", status)?;
+            }
+            Event::Reset { state } => {
+                json.number("zeros", state.zeros)?;
+                json.str("unit", state.zero_unit.as_str())?;
+                json.hex("entropy", &state.entropy)?;
+                json.bool("configured", state.is_configured)?;
+            }
+            Event::Zeros { zeros, unit } => {
+                json.number("zeros", *zeros)?;
+                json.str("unit", unit.as_str())?;
+            }
+            Event::Entropy(entropy) => json.hex("entropy", *entropy)?,
+            Event::Farmer(farmer) => json.display("farmer", AccountId(farmer))?,
+            Event::Block(block_index) => json.number("block", *block_index)?,
+            Event::Range { start, end } => {
+                json.number("start", *start)?;
+                json.number("end", *end)?;
+            }
+            Event::ProgressInterval(interval_ms) => json.number("interval_ms", *interval_ms)?,
+            Event::Hash(algorithm) | Event::HashInfo(algorithm) => json.str("algo", algorithm.name())?,
+            Event::Mode(mode) => json.str("mode", mode.as_str())?,
+            Event::MineStart { block_index, zeros, unit, entropy } => {
+                json.number("block", *block_index)?;
+                json.number("zeros", *zeros)?;
+                json.str("unit", unit.as_str())?;
+                json.hex("entropy", *entropy)?;
+            }
+            Event::Found { result, unit, algorithm }
+            | Event::MineResult { result, unit, algorithm } => {
+                write_result_json(&mut json, result, *unit)?;
+                json.str("algo", algorithm.name())?;
+            }
+            Event::MineBest { result, unit } => write_result_json(&mut json, result, *unit)?,
+            Event::MineExhausted { start, end, best, unit } => {
+                json.number("start", *start)?;
+                json.number("end", *end)?;
+                write_result_json(&mut json, best, *unit)?;
+            }
+            Event::MineStopped(job)
+            | Event::MinePaused(job)
+            | Event::MineResumed(job)
+            | Event::MineProgress(job) => write_job_json(&mut json, job)?,
+            Event::Estimate { zeros, unit, hashrate } => {
+                let expected = ZeroChecker::expected_hashes(*zeros, *unit);
+                json.number("zeros", *zeros)?;
+                json.str("unit", unit.as_str())?;
+                json.number("expected_hashes", expected)?;
+                json.number("hashrate", *hashrate)?;
+                json.optional("eta_s", (*hashrate > 0).then(|| expected / *hashrate as u128))?;
+            }
+            Event::Verify { result, target, unit, algorithm } => {
+                json.bool("ok", result.zeros >= *target as u32)?;
+                json.number("target", *target)?;
+                write_result_json(&mut json, result, *unit)?;
+                json.str("algo", algorithm.name())?;
+            }
+            Event::Digest { algorithm, len, hash } => {
+                json.str("algo", algorithm.name())?;
+                json.number("len", *len as u64)?;
+                json.hex("hash", *hash)?;
+            }
+            Event::Bench { algorithm, bench } => {
+                json.str("algo", algorithm.name())?;
+                json.number("nonces", bench.nonces)?;
+                json.number("elapsed_us", bench.elapsed.as_micros())?;
+                json.number("hashrate", bench.hashrate())?;
+                json.number("ns_per_hash", bench.nanos_per_hash())?;
+            }
+            Event::SelfTestCase { name, ok } => {
+                json.str("name", name)?;
+                json.bool("ok", *ok)?;
+            }
+            Event::SelfTestDone(summary) => {
+                json.bool("ok", summary.all_passed())?;
+                json.number("passed", summary.passed)?;
+                json.number("total", summary.total)?;
+            }
+            Event::Error { source, message } => {
+                json.str("source", source.as_str())?;
+                json.display("message", message)?;
+            }
+            Event::Unknown(cmd) => json.str("command", cmd)?,
+            Event::Nak(error) => {
+                json.number("code", error.code())?;
+                json.display("message", error)?;
+            }
+        }
+        json.end()
+    }
+}
+
+/// Block, nonce, digest and zero count of a result, as JSON fields
+fn write_result_json<W: Write>(json: &mut JsonObject<W>, result: &MiningResult, unit: ZeroUnit) -> fmt::Result {
+    json.number("block", result.block_index)?;
+    json.number("nonce", result.nonce)?;
+    json.hex("hash", &result.hash)?;
+    json.number("zeros", result.zeros)?;
+    json.str("unit", unit.as_str())
+}
+
+/// Progress of a job, as JSON fields
+fn write_job_json<W: Write>(json: &mut JsonObject<W>, job: &MiningJob) -> fmt::Result {
+    json.number("block", job.block_index)?;
+    json.number("tested", job.tested)?;
+    json.number("next_nonce", job.next_nonce)?;
+    json.optional("best_nonce", job.best.map(|best| best.nonce))?;
+    json.number("best_zeros", job.best_zeros())?;
+    json.str("unit", job.zero_unit.as_str())?;
+    json.number("hashrate", job.hashrate())?;
+    json.number("elapsed_ms", job.elapsed().as_millis())?;
+    json.optional("eta_s", job.eta_secs())
+}
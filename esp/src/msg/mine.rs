@@ -1,36 +1,219 @@
 //! Module for mine command messages
 
 
-use core::fmt::Write;
+use core::fmt::{Display, Write};
+use core::str::FromStr;
+
+use super::{parse_number, HashAlgorithm, ParseError, ZeroUnit};
+use crate::hex::Hex;
+use crate::mining::{MiningError, MiningJob, MiningResult, ZeroChecker};
+
+/// Search budget for best-so-far mining
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MiningBudget {
+    /// Number of nonces to test
+    Nonces(u64),
+    /// Wall-clock time in milliseconds
+    Millis(u32),
+}
+
+impl FromStr for MiningBudget {
+    type Err = ParseError;
+
+    /// Parse um budget a partir de uma string (`100000` ou `5000ms`); zero or an overflowing count is out of range
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let budget = match s.strip_suffix("ms") {
+            Some(millis) => MiningBudget::Millis(parse_number(Some(&millis), "budget")?),
+            None => MiningBudget::Nonces(parse_number(Some(&s), "budget")?),
+        };
+        match budget {
+            MiningBudget::Nonces(0) | MiningBudget::Millis(0) => Err(ParseError::OutOfRange("budget")),
+            budget => Ok(budget),
+        }
+    }
+}
 
 /// Sends mining start message
-pub fn send_mine_start_message<W>(uart: &mut W, zeros: u8, entropy: u8) -> Result<(), core::fmt::Error>
+pub fn send_mine_start_message<W>(uart: &mut W, block_index: u32, zeros: u8, unit: ZeroUnit, entropy: &[u8; 32]) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "MINE_START: Iniciando mineração do bloco {} com {} zeros ({}) e entropy {}\r\n", block_index, zeros, unit.as_str(), Hex(entropy))
+}
+
+/// Sends mining result message as in the ROADMAP protocol:
+/// `MINE_RESULT <nonce> <hash_hex> <zeros_count>`, then `key=value` extras
+/// (unit, block, algorithm) - everything `work(farmer, hash, nonce)` needs in one line
+pub fn send_mine_result_message<W>(uart: &mut W, result: &MiningResult, unit: ZeroUnit, algorithm: HashAlgorithm) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "MINE_RESULT ")?;
+    write_result_fields(uart, result, unit, algorithm)
+}
+
+/// Sends message for a nonce that meets the target, same fields as `MINE_RESULT`
+pub fn send_mine_found_message<W>(uart: &mut W, result: &MiningResult, unit: ZeroUnit, algorithm: HashAlgorithm) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "FOUND ")?;
+    write_result_fields(uart, result, unit, algorithm)
+}
+
+/// Sends the recomputed digest of a nonce and whether it meets the target
+pub fn send_verify_message<W>(uart: &mut W, result: &MiningResult, target: u8, unit: ZeroUnit, algorithm: HashAlgorithm) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    let ok = result.zeros >= target as u32;
+    write!(
+        uart,
+        "VERIFY: ok={}, target={} {}, block={}, nonce={}, hash={}, zeros={}, unit={}, algo={}\r\n",
+        ok,
+        target,
+        unit.as_str(),
+        result.block_index,
+        result.nonce,
+        Hex(&result.hash),
+        result.zeros,
+        unit.as_str(),
+        algorithm.as_str()
+    )
+}
+
+/// Writes `<nonce> <hash_hex> <zeros> unit= block= algo=` and ends the line
+fn write_result_fields<W>(uart: &mut W, result: &MiningResult, unit: ZeroUnit, algorithm: HashAlgorithm) -> Result<(), core::fmt::Error>
 where
     W: Write,
 {
-    write!(uart, "MINE_START: Iniciando mineração com {} zeros e entropy {}\r\n", zeros, entropy)
+    write!(
+        uart,
+        "{} {} {} unit={} block={} algo={}\r\n",
+        result.nonce,
+        Hex(&result.hash),
+        result.zeros,
+        unit.as_str(),
+        result.block_index,
+        algorithm.as_str()
+    )
 }
 
-/// Sends mining result message
-pub fn send_mine_result_message<W>(uart: &mut W, nonce: u32) -> Result<(), core::fmt::Error>
+/// Sends message for a new best hash during best-so-far mining
+pub fn send_mine_best_message<W>(uart: &mut W, result: &MiningResult, unit: ZeroUnit) -> Result<(), core::fmt::Error>
 where
     W: Write,
 {
-    write!(uart, "MINE_RESULT: Nonce encontrado: {}\r\n", nonce)
+    write!(uart, "MINE_BEST: block={}, nonce={}, zeros={} {}\r\n", result.block_index, result.nonce, result.zeros, unit.as_str())
+}
+
+/// Sends message for a nonce range tested without reaching the target
+pub fn send_mine_exhausted_message<W>(uart: &mut W, start: u64, end: u64, best: &MiningResult, unit: ZeroUnit) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "MINE_EXHAUSTED: block={}, range={}-{}, best_nonce={}, zeros={} {}\r\n", best.block_index, start, end, best.nonce, best.zeros, unit.as_str())
+}
+
+/// Sends confirmation message for range command
+pub fn send_range_message<W>(uart: &mut W, start: u64, end: u64) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "RANGE: nonces {}-{} configurados para mineração\r\n", start, end)
+}
+
+/// Sends the summary of an aborted job
+pub fn send_mine_stopped_message<W>(uart: &mut W, job: &MiningJob) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "MINE_STOPPED: {} - block={}, testados={}, proximo_nonce={}, tempo={}ms", MiningError::Aborted.as_str(), job.block_index, job.tested, job.next_nonce, job.elapsed().as_millis())?;
+    match job.best {
+        Some(best) => write!(uart, ", best_nonce={}, zeros={} {}\r\n", best.nonce, best.zeros, job.zero_unit.as_str()),
+        None => write!(uart, "\r\n"),
+    }
+}
+
+/// Sends confirmation message for pause command
+pub fn send_mine_paused_message<W>(uart: &mut W, job: &MiningJob) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "MINE_PAUSED: block={}, nonce={}, testados={}\r\n", job.block_index, job.next_nonce, job.tested)
+}
+
+/// Sends confirmation message for resume command
+pub fn send_mine_resumed_message<W>(uart: &mut W, job: &MiningJob) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "MINE_RESUMED: block={}, nonce={}, testados={}\r\n", job.block_index, job.next_nonce, job.tested)
+}
+
+/// Sends confirmation message for progress command
+pub fn send_progress_message<W>(uart: &mut W, interval_ms: u32) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    if interval_ms == 0 {
+        write!(uart, "PROGRESS: mensagens de progresso desativadas\r\n")
+    } else {
+        write!(uart, "PROGRESS: progresso a cada {} ms\r\n", interval_ms)
+    }
 }
 
 /// Envia mensagem de erro para comando mine
-pub fn send_mine_error_message<W>(uart: &mut W, error: &str) -> Result<(), core::fmt::Error>
+pub fn send_mine_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
 where
     W: Write,
+    E: Display,
 {
     write!(uart, "MINE_ERROR: {}\r\n", error)
 }
 
-/// Sends mining progress message
-pub fn send_mine_progress_message<W>(uart: &mut W, nonce: u32, entropy: u8) -> Result<(), core::fmt::Error>
+/// Sends error message for a rejected START_MINING
+pub fn send_job_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+    E: Display,
+{
+    write!(uart, "MINE_ERROR: START_MINING inválido: {}\r\n", error)
+}
+
+/// Sends mining progress message as in the ROADMAP protocol:
+/// `MINE_PROGRESS <nonces_tested> <best_zeros> <hashrate>`, then
+/// `key=value` extras (unit, block, next nonce, ETA)
+pub fn send_mine_progress_message<W>(uart: &mut W, job: &MiningJob) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(
+        uart,
+        "MINE_PROGRESS {} {} {} unit={} block={} nonce={}",
+        job.tested,
+        job.best_zeros(),
+        job.hashrate(),
+        job.zero_unit.as_str(),
+        job.block_index,
+        job.next_nonce
+    )?;
+    match job.eta_secs() {
+        Some(eta) => write!(uart, " eta={}s\r\n", eta),
+        None => write!(uart, " eta=?\r\n"),
+    }
+}
+
+/// Sends the expected work for a difficulty at the measured hashrate
+pub fn send_estimate_message<W>(uart: &mut W, zeros: u8, unit: ZeroUnit, hashrate: u64) -> Result<(), core::fmt::Error>
 where
     W: Write,
 {
-    write!(uart, "MINE_PROGRESS: nonce={}, entropy={}\r\n", nonce, entropy)
-}
\ No newline at end of file
+    let expected = ZeroChecker::expected_hashes(zeros, unit);
+    write!(uart, "ESTIMATE: zeros={} {}, hashes_esperados={}, hashrate={} H/s, tempo=", zeros, unit.as_str(), expected, hashrate)?;
+    if hashrate == 0 {
+        write!(uart, "?\r\n")
+    } else {
+        write!(uart, "{}s\r\n", expected / hashrate as u128)
+    }
+}
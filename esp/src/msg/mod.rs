@@ -1,7 +1,8 @@
 //! Message module for serial communication
 //!
 //! This module contains all messages and commands supported
-//! by the ESP32 serial communication system.
+//! by the ESP32 serial communication system. Every output is an `Event`,
+//! written by the text, JSON or binary encoder of the console mode.
 
 pub mod help;
 pub mod info;
@@ -11,15 +12,53 @@ pub mod zeros;
 pub mod entropy;
 pub mod mine;
 pub mod hash;
+pub mod farmer;
+pub mod block;
+pub mod error;
+pub mod digest;
+pub mod selftest;
+pub mod bench;
+pub mod mode;
+pub mod event;
+pub mod binary;
+pub mod text;
+pub mod json;
+pub mod hello;
 
 // Re-export main functions
-pub use help::send_help_message;
+pub use help::{send_help_message, send_banner_message};
 pub use info::send_info_message;
 pub use reset::send_reset_message;
 pub use unknown::send_unknown_command_message;
-pub use zeros::{send_zeros_message, send_zeros_error_message};
+pub use zeros::{send_zeros_message, send_zeros_error_message, ZeroUnit};
 pub use entropy::{send_entropy_message, send_entropy_error_message};
-pub use mine::{send_mine_start_message, send_mine_result_message, send_mine_error_message, send_mine_progress_message};
+pub use farmer::{send_farmer_message, send_farmer_error_message};
+pub use block::{send_block_message, send_block_error_message};
+pub use error::{send_error_message, ParseError};
+pub use digest::{send_digest_message, send_digest_error_message, MAX_DIGEST_INPUT};
+pub use selftest::{send_selftest_case_message, send_selftest_done_message};
+pub use bench::{send_bench_message, send_bench_error_message, DEFAULT_BENCH_NONCES, MAX_BENCH_NONCES};
+pub use mode::{send_mode_message, send_mode_error_message, OutputMode};
+pub use event::{Event, ErrorSource, Sink};
+pub use hello::{send_hello_message, PROTOCOL_VERSION, FIRMWARE_VERSION, MAX_LINE_LEN, BAUD_RATE};
+pub use mine::{send_mine_start_message, send_mine_result_message, send_mine_error_message, send_mine_progress_message, send_mine_best_message, send_mine_found_message, send_mine_exhausted_message, send_range_message, send_mine_stopped_message, send_mine_paused_message, send_mine_resumed_message, send_progress_message, send_job_error_message, send_estimate_message, send_verify_message, MiningBudget};
+use core::num::IntErrorKind;
+use core::str::FromStr;
+
+use crate::hex;
+use crate::strkey;
+use crate::mining::{JobParams, ZeroChecker, MAX_BATCH_SIZE};
+
+/// Maximum number of whitespace-separated tokens in a command line
+const MAX_TOKENS: usize = 8;
+
+/// Every command word accepted by `Command::from_str`
+pub const COMMAND_NAMES: [&str; 22] = [
+    "help", "info", "reset", "zeros", "entropy", "farmer", "block", "start_mining", "mine",
+    "estimate", "verify", "range", "progress", "stop", "pause", "resume", "hash", "digest",
+    "selftest", "bench", "mode", "hello",
+];
+
 pub use hash::{send_hash_message, send_hash_error_message, send_current_hash_message, HashAlgorithm};
 
 /// Enum para representar os diferentes tipos de comando
@@ -28,78 +67,227 @@ pub enum Command {
     Help,
     Info,
     Reset,
-    Zeros(u8),
-    Entropy(u8),
-    Mine,
+    Zeros(u8, Option<ZeroUnit>),
+    Entropy([u8; 32]),
+    Farmer([u8; 32]),
+    Block(u32),
+    StartMining(JobParams),
+    Mine(Option<MiningBudget>),
+    Estimate,
+    Verify(u64),
+    Range(u64, u64),
+    Progress(u32),
+    Stop,
+    Pause,
+    Resume,
     Hash(HashAlgorithm),
     HashInfo,
-    Unknown(heapless::String<64>),
+    Digest(HashAlgorithm, heapless::Vec<u8, MAX_DIGEST_INPUT>),
+    SelfTest,
+    Bench(Option<HashAlgorithm>, u32),
+    Mode(OutputMode),
+    Hello,
 }
 
 impl Command {
     /// Parse um comando a partir de uma string
-    pub fn from_str(cmd: &str) -> Self {
+    pub fn from_str(cmd: &str) -> Result<Self, ParseError> {
         let cmd = cmd.trim();
-        let parts: heapless::Vec<&str, 3> = cmd.split_whitespace().collect();
-        
-        if parts.is_empty() {
-            return Command::Unknown(heapless::String::new());
+        let mut parts: heapless::Vec<&str, MAX_TOKENS> = heapless::Vec::new();
+        for part in cmd.split_whitespace() {
+            parts.push(part).map_err(|_| ParseError::TooManyArguments)?;
         }
         
-        let command = parts[0];
+        let Some(&command) = parts.first() else {
+            return Err(ParseError::UnknownCommand);
+        };
+        let args = &parts[1..];
         
         if command.eq_ignore_ascii_case("help") {
-            Command::Help
+            Ok(Command::Help)
+        } else if command.eq_ignore_ascii_case("hello") {
+            Ok(Command::Hello)
         } else if command.eq_ignore_ascii_case("info") {
-            Command::Info
+            Ok(Command::Info)
         } else if command.eq_ignore_ascii_case("reset") {
-            Command::Reset
+            Ok(Command::Reset)
         } else if command.eq_ignore_ascii_case("zeros") {
-            if parts.len() >= 2 {
-                if let Ok(value) = parts[1].parse::<u8>() {
-                    Command::Zeros(value)
-                } else {
-                    let mut unknown_cmd = heapless::String::new();
-                    let _ = unknown_cmd.push_str(cmd);
-                    Command::Unknown(unknown_cmd)
-                }
-            } else {
-                let mut unknown_cmd = heapless::String::new();
-                let _ = unknown_cmd.push_str(cmd);
-                Command::Unknown(unknown_cmd)
+            // Optional unit: `zeros 8 nibbles`
+            let zeros = parse_number::<u8>(args.first(), "zeros")?;
+            let unit = match args.get(1) {
+                Some(unit) => Some(unit.parse()?),
+                None => None,
+            };
+            // Without a unit, feasibility depends on the current one - checked by the caller
+            if unit.is_some_and(|unit| !ZeroChecker::is_feasible(zeros, unit)) {
+                return Err(ParseError::OutOfRange("zeros"));
             }
+            Ok(Command::Zeros(zeros, unit))
         } else if command.eq_ignore_ascii_case("entropy") {
-            if parts.len() >= 2 {
-                if let Ok(value) = parts[1].parse::<u8>() {
-                    Command::Entropy(value)
-                } else {
-                    let mut unknown_cmd = heapless::String::new();
-                    let _ = unknown_cmd.push_str(cmd);
-                    Command::Unknown(unknown_cmd)
-                }
-            } else {
-                let mut unknown_cmd = heapless::String::new();
-                let _ = unknown_cmd.push_str(cmd);
-                Command::Unknown(unknown_cmd)
-            }
+            // Previous block hash: `entropy <64 hex chars>`
+            let entropy = args.first().ok_or(ParseError::MissingArgument("entropy"))?;
+            hex::decode_array::<32>(entropy)
+                .map(Command::Entropy)
+                .map_err(|error| ParseError::InvalidHex("entropy", error))
+        } else if command.eq_ignore_ascii_case("farmer") {
+            // Farmer address: `farmer G...`
+            let farmer = args.first().ok_or(ParseError::MissingArgument("farmer"))?;
+            strkey::decode_account_id(farmer)
+                .map(Command::Farmer)
+                .map_err(ParseError::InvalidStrkey)
+        } else if command.eq_ignore_ascii_case("block") {
+            Ok(Command::Block(parse_number(args.first(), "block")?))
+        } else if command.eq_ignore_ascii_case("start_mining") {
+            // Whole job in one line - nothing is applied unless every field is valid
+            Ok(Command::StartMining(Self::parse_job(args)?))
         } else if command.eq_ignore_ascii_case("mine") {
-            Command::Mine
+            // Optional budget switches to best-so-far mode: `mine 100000` or `mine 5000ms`
+            match args.first() {
+                Some(budget) => Ok(Command::Mine(Some(budget.parse()?))),
+                None => Ok(Command::Mine(None)),
+            }
+        } else if command.eq_ignore_ascii_case("estimate") {
+            Ok(Command::Estimate)
+        } else if command.eq_ignore_ascii_case("verify") {
+            Ok(Command::Verify(parse_number(args.first(), "nonce")?))
+        } else if command.eq_ignore_ascii_case("stop") {
+            Ok(Command::Stop)
+        } else if command.eq_ignore_ascii_case("pause") {
+            Ok(Command::Pause)
+        } else if command.eq_ignore_ascii_case("resume") {
+            Ok(Command::Resume)
+        } else if command.eq_ignore_ascii_case("progress") {
+            Ok(Command::Progress(parse_number(args.first(), "interval")?))
+        } else if command.eq_ignore_ascii_case("range") {
+            // Nonce range assigned by the host: `range <start> <end>` (end inclusive)
+            let start = parse_number::<u64>(args.first(), "start")?;
+            let end = parse_number::<u64>(args.get(1), "end")?;
+            if start > end {
+                return Err(ParseError::OutOfRange("end"));
+            }
+            Ok(Command::Range(start, end))
         } else if command.eq_ignore_ascii_case("hash") {
-            if parts.len() >= 2 {
-                if let Some(algorithm) = HashAlgorithm::from_str(parts[1]) {
-                    Command::Hash(algorithm)
-                } else {
-                    let mut unknown_cmd = heapless::String::new();
-                    let _ = unknown_cmd.push_str(cmd);
-                    Command::Unknown(unknown_cmd)
+            match args.first() {
+                Some(algorithm) => HashAlgorithm::from_str(algorithm)
+                    .map(Command::Hash)
+                    .ok_or(ParseError::UnsupportedAlgorithm),
+                None => Ok(Command::HashInfo),
+            }
+        } else if command.eq_ignore_ascii_case("selftest") {
+            Ok(Command::SelfTest)
+        } else if command.eq_ignore_ascii_case("bench") {
+            // `bench`, `bench keccak256`, `bench 5000` or `bench sha256 5000`
+            let algorithm = args.first().and_then(|arg| HashAlgorithm::from_str(arg));
+            let count = if algorithm.is_some() { args.get(1) } else { args.first() };
+            let nonces = match count {
+                Some(count) if algorithm.is_none() && !count.starts_with(|c: char| c.is_ascii_digit()) => {
+                    return Err(ParseError::UnsupportedAlgorithm);
                 }
-            } else {
-                Command::HashInfo
+                Some(_) => parse_number(count, "n")?,
+                None => DEFAULT_BENCH_NONCES,
+            };
+            if nonces == 0 || nonces > MAX_BENCH_NONCES {
+                return Err(ParseError::OutOfRange("n"));
             }
+            Ok(Command::Bench(algorithm, nonces))
+        } else if command.eq_ignore_ascii_case("digest") {
+            // Arbitrary input: `digest keccak256 <hex>` (`0x` alone hashes the empty string)
+            let algorithm = args.first().ok_or(ParseError::MissingArgument("algo"))?;
+            let algorithm = HashAlgorithm::from_str(algorithm).ok_or(ParseError::UnsupportedAlgorithm)?;
+            let data = args.get(1).ok_or(ParseError::MissingArgument("data"))?;
+            let mut buf = [0u8; MAX_DIGEST_INPUT];
+            let data = hex::decode_into(data, &mut buf)
+                .map_err(|error| ParseError::InvalidHex("data", error))?;
+            let data = heapless::Vec::from_slice(data).map_err(|_| ParseError::OutOfRange("data"))?;
+            Ok(Command::Digest(algorithm, data))
+        } else if command.eq_ignore_ascii_case("mode") {
+            // Console protocol: `mode binary` (back to text with a MODE frame)
+            let mode = args.first().ok_or(ParseError::MissingArgument("mode"))?;
+            Ok(Command::Mode(mode.parse()?))
+        } else {
+            Err(ParseError::UnknownCommand)
+        }
+    }
+
+    /// Parses `<block_index> <entropy_hex> <farmer_hex> <difficulty> <batch_size>`
+    fn parse_job(args: &[&str]) -> Result<JobParams, ParseError> {
+        if args.len() > 5 {
+            return Err(ParseError::TooManyArguments);
+        }
+
+        let block_index = parse_number(args.first(), "block_index")?;
+        let entropy = args.get(1).ok_or(ParseError::MissingArgument("entropy_hex"))?;
+        let entropy = hex::decode_array::<32>(entropy)
+            .map_err(|error| ParseError::InvalidHex("entropy_hex", error))?;
+        // Farmer as raw hex or as a G... strkey (hex never starts with 'G')
+        let farmer = args.get(2).ok_or(ParseError::MissingArgument("farmer_hex"))?;
+        let farmer = if farmer.starts_with('G') {
+            strkey::decode_account_id(farmer).map_err(ParseError::InvalidStrkey)?
         } else {
-            let mut unknown_cmd = heapless::String::new();
-            let _ = unknown_cmd.push_str(cmd);
-            Command::Unknown(unknown_cmd)
+            hex::decode_array::<32>(farmer)
+                .map_err(|error| ParseError::InvalidHex("farmer_hex", error))?
+        };
+        let zeros = parse_number(args.get(3), "difficulty")?;
+        let batch_size = parse_number(args.get(4), "batch_size")?;
+
+        Self::validate_job(JobParams {
+            block_index,
+            entropy,
+            farmer,
+            zeros,
+            batch_size,
+        })
+    }
+
+    /// Range checks shared by the text and binary `START_MINING`
+    pub(crate) fn validate_job(job: JobParams) -> Result<JobParams, ParseError> {
+        // All-zero entropy or farmer means "not configured" to `MiningState`
+        if job.entropy == [0u8; 32] {
+            return Err(ParseError::OutOfRange("entropy_hex"));
         }
+        if job.farmer == [0u8; 32] {
+            return Err(ParseError::OutOfRange("farmer_hex"));
+        }
+        if job.zeros == 0 || !ZeroChecker::is_feasible(job.zeros, JobParams::ZERO_UNIT) {
+            return Err(ParseError::OutOfRange("difficulty"));
+        }
+        if job.batch_size == 0 || job.batch_size > MAX_BATCH_SIZE {
+            return Err(ParseError::OutOfRange("batch_size"));
+        }
+        Ok(job)
+    }
+}
+
+/// Command line with its optional `#<id>` prefix, e.g. `#7 zeros 4`
+#[derive(Debug, PartialEq)]
+pub struct Request<'a> {
+    pub id: Option<u32>,
+    pub cmd: &'a str,
+}
+
+impl<'a> Request<'a> {
+    /// Splits the id off a line; a malformed id fails the whole line
+    pub fn from_line(line: &'a str) -> Result<Self, ParseError> {
+        let line = line.trim();
+        let Some(tagged) = line.strip_prefix('#') else {
+            return Ok(Self { id: None, cmd: line });
+        };
+        let (id, cmd) = tagged.split_once(char::is_whitespace).unwrap_or((tagged, ""));
+        Ok(Self {
+            id: Some(parse_number(Some(&id), "id")?),
+            cmd: cmd.trim_start(),
+        })
     }
-}
\ No newline at end of file
+}
+
+/// Parses a required decimal argument, telling malformed numbers from overflowing ones
+fn parse_number<T>(arg: Option<&&str>, name: &'static str) -> Result<T, ParseError>
+where
+    T: FromStr<Err = core::num::ParseIntError>,
+{
+    let arg = arg.ok_or(ParseError::MissingArgument(name))?;
+    arg.parse::<T>().map_err(|error| match error.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ParseError::OutOfRange(name),
+        _ => ParseError::InvalidNumber(name),
+    })
+}
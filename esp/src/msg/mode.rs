@@ -0,0 +1,66 @@
+//! Module for console protocol selection
+
+
+use core::fmt::{Display, Write};
+use core::str::FromStr;
+
+use super::ParseError;
+
+/// Protocol spoken on the console
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputMode {
+    /// CR/LF-terminated text lines
+    Text,
+    /// COBS frames with CRC (see `frame`)
+    Binary,
+    /// One JSON object per line (see `Event::write_json`)
+    Json,
+}
+
+impl FromStr for OutputMode {
+    type Err = ParseError;
+
+    /// Parse um modo a partir de uma string
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        if s.eq_ignore_ascii_case("text") {
+            Ok(OutputMode::Text)
+        } else if s.eq_ignore_ascii_case("binary") {
+            Ok(OutputMode::Binary)
+        } else if s.eq_ignore_ascii_case("json") {
+            Ok(OutputMode::Json)
+        } else {
+            Err(ParseError::UnsupportedMode)
+        }
+    }
+}
+
+impl OutputMode {
+    /// Every mode, in wire order
+    pub const ALL: [OutputMode; 3] = [OutputMode::Text, OutputMode::Binary, OutputMode::Json];
+
+    /// Converte o modo para string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputMode::Text => "text",
+            OutputMode::Binary => "binary",
+            OutputMode::Json => "json",
+        }
+    }
+}
+
+/// Sends confirmation message for mode command
+pub fn send_mode_message<W>(uart: &mut W, mode: OutputMode) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "MODE: {}\r\n", mode.as_str())
+}
+
+/// Sends error message for invalid mode command
+pub fn send_mode_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+    E: Display,
+{
+    write!(uart, "MODE_ERROR: {}\r\n", error)
+}
@@ -1,24 +1,20 @@
 //! Counter reset confirmation message
 
-use esp_hal::uart::Uart;
-use esp_hal::DriverMode;
 use core::fmt::Write;
 
+use crate::hex::Hex;
 use crate::mining::MiningState;
 
 
 /// Sends counter reset confirmation message
-pub fn send_reset_message<D>(uart: &mut Uart<D>, mining_state: &mut MiningState) -> Result<(), core::fmt::Error>
+pub fn send_reset_message<W>(uart: &mut W, mining_state: &MiningState) -> Result<(), core::fmt::Error>
 where
-    D: DriverMode,
+    W: Write,
 {
-    // Reset mining state using specific method
-    mining_state.reset();
-    
     writeln!(uart, "=== Reset Executado ===")?;
     writeln!(uart, "Estado de mineração reiniciado:")?;
-    writeln!(uart, "Zeros: {}", mining_state.zeros)?;
-    writeln!(uart, "Entropy: {}", mining_state.entropy)?;
+    writeln!(uart, "Zeros: {} ({})", mining_state.zeros, mining_state.zero_unit.as_str())?;
+    writeln!(uart, "Entropy: {}", Hex(&mining_state.entropy))?;
     writeln!(uart, "Configurado: {}", mining_state.is_configured)?;
     match mining_state.last_nonce {
         Some(nonce) => writeln!(uart, "Último nonce: {}", nonce)?,
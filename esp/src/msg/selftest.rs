@@ -0,0 +1,23 @@
+//! Module for self-test messages
+
+
+use core::fmt::Write;
+
+use crate::selftest::SelfTestSummary;
+
+/// Sends the PASS/FAIL line of one known-answer test
+pub fn send_selftest_case_message<W>(uart: &mut W, name: &str, ok: bool) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    write!(uart, "SELFTEST: {} {}\r\n", if ok { "PASS" } else { "FAIL" }, name)
+}
+
+/// Sends the summary line after every case ran
+pub fn send_selftest_done_message<W>(uart: &mut W, summary: &SelfTestSummary) -> Result<(), core::fmt::Error>
+where
+    W: Write,
+{
+    let status = if summary.all_passed() { "PASS" } else { "FAIL" };
+    write!(uart, "SELFTEST_DONE: {} - {}/{} casos\r\n", status, summary.passed, summary.total)
+}
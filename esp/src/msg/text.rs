@@ -0,0 +1,91 @@
+//! Text encoder for device events
+//!
+//! The human-readable console: each event goes to the `send_*` function of
+//! its message module, errors to the `*_ERROR` prefix of their command.
+//! Replies to a `#<id>` request get the id in front of every line.
+
+use core::fmt::{self, Write};
+
+use super::*;
+
+impl Event<'_> {
+    /// Writes the event as the text console lines
+    pub fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        match self {
+            Event::Ready => send_banner_message(out),
+            Event::Help => send_help_message(out),
+            Event::Hello => send_hello_message(out),
+            Event::Info { state, job } => send_info_message(out, state, *job),
+            Event::Reset { state } => send_reset_message(out, state),
+            Event::Zeros { zeros, unit } => send_zeros_message(out, *zeros, *unit),
+            Event::Entropy(entropy) => send_entropy_message(out, entropy),
+            Event::Farmer(farmer) => send_farmer_message(out, farmer),
+            Event::Block(block_index) => send_block_message(out, *block_index),
+            Event::Range { start, end } => send_range_message(out, *start, *end),
+            Event::ProgressInterval(interval_ms) => send_progress_message(out, *interval_ms),
+            Event::Hash(algorithm) => send_hash_message(out, *algorithm),
+            Event::HashInfo(algorithm) => send_current_hash_message(out, *algorithm),
+            Event::Mode(mode) => send_mode_message(out, *mode),
+            Event::MineStart { block_index, zeros, unit, entropy } => {
+                send_mine_start_message(out, *block_index, *zeros, *unit, entropy)
+            }
+            Event::Found { result, unit, algorithm } => send_mine_found_message(out, result, *unit, *algorithm),
+            Event::MineResult { result, unit, algorithm } => send_mine_result_message(out, result, *unit, *algorithm),
+            Event::MineBest { result, unit } => send_mine_best_message(out, result, *unit),
+            Event::MineExhausted { start, end, best, unit } => send_mine_exhausted_message(out, *start, *end, best, *unit),
+            Event::MineStopped(job) => send_mine_stopped_message(out, job),
+            Event::MinePaused(job) => send_mine_paused_message(out, job),
+            Event::MineResumed(job) => send_mine_resumed_message(out, job),
+            Event::MineProgress(job) => send_mine_progress_message(out, job),
+            Event::Estimate { zeros, unit, hashrate } => send_estimate_message(out, *zeros, *unit, *hashrate),
+            Event::Verify { result, target, unit, algorithm } => {
+                send_verify_message(out, result, *target, *unit, *algorithm)
+            }
+            Event::Digest { algorithm, len, hash } => send_digest_message(out, *algorithm, *len, hash),
+            Event::Bench { algorithm, bench } => send_bench_message(out, *algorithm, bench),
+            Event::SelfTestCase { name, ok } => send_selftest_case_message(out, name, *ok),
+            Event::SelfTestDone(summary) => send_selftest_done_message(out, summary),
+            Event::Error { source, message } => match source {
+                ErrorSource::Command => send_error_message(out, message),
+                ErrorSource::Zeros => send_zeros_error_message(out, message),
+                ErrorSource::Entropy => send_entropy_error_message(out, message),
+                ErrorSource::Farmer => send_farmer_error_message(out, message),
+                ErrorSource::Block => send_block_error_message(out, message),
+                ErrorSource::Hash => send_hash_error_message(out, message),
+                ErrorSource::Mine => send_mine_error_message(out, message),
+                ErrorSource::StartMining => send_job_error_message(out, message),
+                ErrorSource::Digest => send_digest_error_message(out, message),
+                ErrorSource::Bench => send_bench_error_message(out, message),
+                ErrorSource::Mode => send_mode_error_message(out, message),
+            },
+            Event::Unknown(cmd) => send_unknown_command_message(out, cmd),
+            Event::Nak(error) => send_error_message(out, error),
+        }
+    }
+}
+
+/// Prefixes every line written through it with `#<id> `
+pub(crate) struct Tagged<'a, W: Write> {
+    out: &'a mut W,
+    id: u32,
+    line_start: bool,
+}
+
+impl<'a, W: Write> Tagged<'a, W> {
+    pub(crate) fn new(out: &'a mut W, id: u32) -> Self {
+        Self { out, id, line_start: true }
+    }
+}
+
+impl<W: Write> Write for Tagged<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for line in s.split_inclusive('\n') {
+            if self.line_start {
+                write!(self.out, "#{} ", self.id)?;
+            }
+            self.out.write_str(line)?;
+            self.line_start = line.ends_with('\n');
+        }
+        Ok(())
+    }
+}
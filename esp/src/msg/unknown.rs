@@ -1,18 +1,14 @@
 //! Message for unrecognized commands
 
-use esp_hal::uart::Uart;
-use esp_hal::DriverMode;
-use heapless::String;
 use core::fmt::Write;
 
 /// Sends message for unrecognized command
-pub fn send_unknown_command_message<D>(uart: &mut Uart<D>, cmd: &str) -> Result<(), core::fmt::Error>
+pub fn send_unknown_command_message<W>(uart: &mut W, cmd: &str) -> Result<(), core::fmt::Error>
 where
-    D: DriverMode,
+    W: Write,
 {
-    let mut response: String<128> = String::new();
-    write!(response, "Comando recebido: '{}'\r\n", cmd)?;
-    write!(uart, "{}", response)?;
+    // Straight to the output - a full console line does not fit a small buffer
+    write!(uart, "Comando recebido: '{}'\r\n", cmd)?;
     write!(uart, "Digite 'help' para ver comandos disponíveis\r\n")?;
     Ok(())
 }
\ No newline at end of file
@@ -1,20 +1,80 @@
 //! Module for zeros command messages
 
 
-use core::fmt::Write;
+use core::fmt::{Display, Write};
+use core::str::FromStr;
+
+use super::ParseError;
+
+/// Digest size of every supported hash algorithm
+const DIGEST_BITS: u32 = 256;
+
+/// Unit used to interpret the required number of leading zeros
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZeroUnit {
+    Bits,
+    Nibbles,
+    Bytes,
+}
+
+impl FromStr for ZeroUnit {
+    type Err = ParseError;
+
+    /// Parse uma unidade a partir de uma string
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        if s.eq_ignore_ascii_case("bits") || s.eq_ignore_ascii_case("bit") {
+            Ok(ZeroUnit::Bits)
+        } else if s.eq_ignore_ascii_case("nibbles") || s.eq_ignore_ascii_case("nibble") {
+            Ok(ZeroUnit::Nibbles)
+        } else if s.eq_ignore_ascii_case("bytes") || s.eq_ignore_ascii_case("byte") {
+            Ok(ZeroUnit::Bytes)
+        } else {
+            Err(ParseError::UnsupportedUnit)
+        }
+    }
+}
+
+impl ZeroUnit {
+    /// Every unit, in wire order
+    pub const ALL: [ZeroUnit; 3] = [ZeroUnit::Bits, ZeroUnit::Nibbles, ZeroUnit::Bytes];
+
+    /// Number of bits in one unit
+    pub fn bits(&self) -> u32 {
+        match self {
+            ZeroUnit::Bits => 1,
+            ZeroUnit::Nibbles => 4,
+            ZeroUnit::Bytes => 8,
+        }
+    }
+    
+    /// Most zeros a 256-bit digest can have in this unit
+    pub fn max_zeros(&self) -> u32 {
+        DIGEST_BITS / self.bits()
+    }
+    
+    /// Converte a unidade para string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ZeroUnit::Bits => "bits",
+            ZeroUnit::Nibbles => "nibbles",
+            ZeroUnit::Bytes => "bytes",
+        }
+    }
+}
 
 /// Sends confirmation message for zeros command
-pub fn send_zeros_message<W>(uart: &mut W, zeros: u8) -> Result<(), core::fmt::Error>
+pub fn send_zeros_message<W>(uart: &mut W, zeros: u8, unit: ZeroUnit) -> Result<(), core::fmt::Error>
 where
     W: Write,
 {
-    write!(uart, "ZEROS: {} zeros configurados para mineração\r\n", zeros)
+    write!(uart, "ZEROS: {} {} configurados para mineração\r\n", zeros, unit.as_str())
 }
 
 /// Sends error message for invalid zeros command
-pub fn send_zeros_error_message<W>(uart: &mut W, error: &str) -> Result<(), core::fmt::Error>
+pub fn send_zeros_error_message<W, E>(uart: &mut W, error: E) -> Result<(), core::fmt::Error>
 where
     W: Write,
+    E: Display,
 {
     write!(uart, "ZEROS_ERROR: {}\r\n", error)
 }
\ No newline at end of file
@@ -0,0 +1,233 @@
+//! Self-test module - known-answer tests for the hashers and the zero counter
+//!
+//! Runs on the device (`selftest` command and once at boot), so the hashers
+//! are checked on real silicon. Every expected digest was cross-checked
+//! against independent SHA-256 and Keccak-256 reference implementations.
+
+use crate::hex;
+use crate::mining::{Hasher, KalePreimage, Keccak256Hasher, Sha256Hasher, ZeroChecker, KALE_PREIMAGE_LEN};
+use crate::msg::{HashAlgorithm, ZeroUnit};
+
+/// Digest of a fixed input
+struct DigestVector {
+    name: &'static str,
+    algorithm: HashAlgorithm,
+    input: &'static [u8],
+    expected: &'static str,
+}
+
+const DIGEST_VECTORS: [DigestVector; 4] = [
+    DigestVector {
+        name: "sha256 vazio",
+        algorithm: HashAlgorithm::Sha256,
+        input: b"",
+        expected: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+    },
+    DigestVector {
+        name: "sha256 abc",
+        algorithm: HashAlgorithm::Sha256,
+        input: b"abc",
+        expected: "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+    },
+    DigestVector {
+        name: "keccak256 vazio",
+        algorithm: HashAlgorithm::Keccak256,
+        input: b"",
+        expected: "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+    },
+    DigestVector {
+        name: "keccak256 abc",
+        algorithm: HashAlgorithm::Keccak256,
+        input: b"abc",
+        expected: "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+    },
+];
+
+/// Synthetic KALE job used by the preimage vectors: block 12345, entropy
+/// 00..1f and farmer GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7
+///
+/// Not taken from a harvested block - no kale-farmer log or contract test
+/// vector is available to this repo yet. The vectors pin the preimage layout
+/// of the contract's `work()` (`KALE_PREIMAGE` below, written out by hand
+/// from that layout) and the digests over it, which were recomputed with
+/// Python's `hashlib` (SHA-256) and a standalone Keccak-f[1600] (Keccak-256).
+/// They prove the device hashes that layout correctly, not that it matches
+/// the chain; a (block, entropy, farmer, nonce, hash) tuple from a real
+/// `work()` call should be added once one is recorded.
+const KALE_BLOCK_INDEX: u32 = 12345;
+const KALE_FARMER: &str = "019472628ee78eb92714d22d0390963c24d927ea009999486e891fac2daa9c21";
+
+/// Full 76-byte preimage of the synthetic job for nonce 11160:
+/// block | nonce | entropy | farmer, integers big-endian
+const KALE_PREIMAGE_NONCE: u64 = 11160;
+const KALE_PREIMAGE: &str = concat!(
+    "00003039",
+    "0000000000002b98",
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "019472628ee78eb92714d22d0390963c24d927ea009999486e891fac2daa9c21",
+);
+
+/// Digest of the KALE preimage for a nonce, with its leading zero nibbles
+struct KaleVector {
+    name: &'static str,
+    algorithm: HashAlgorithm,
+    nonce: u64,
+    expected: &'static str,
+    nibbles: u32,
+}
+
+const KALE_VECTORS: [KaleVector; 4] = [
+    KaleVector {
+        name: "kale keccak256 nonce 0",
+        algorithm: HashAlgorithm::Keccak256,
+        nonce: 0,
+        expected: "e5bc661f17e837115098c81dfc54df7ef53f1e7130953dca7289f2256ea1e76a",
+        nibbles: 0,
+    },
+    KaleVector {
+        name: "kale keccak256 nonce 11160",
+        algorithm: HashAlgorithm::Keccak256,
+        nonce: 11160,
+        expected: "000059d1dbf35a6f26fe2d7ac82c968bf2e428c5428dd5de02f9b7d84fd5a488",
+        nibbles: 4,
+    },
+    KaleVector {
+        name: "kale sha256 nonce 0",
+        algorithm: HashAlgorithm::Sha256,
+        nonce: 0,
+        expected: "4bcfbbe43ca2c486deb0dc3d215e1db8a411067d9e2937355e610dc723b570ae",
+        nibbles: 0,
+    },
+    KaleVector {
+        name: "kale sha256 nonce 18122",
+        algorithm: HashAlgorithm::Sha256,
+        nonce: 18122,
+        expected: "00007478c04d50e90e2f65ffcd981a12c0f6bc37d1cd6f59c41d1b655f5a7030",
+        nibbles: 4,
+    },
+];
+
+/// Leading zeros of a digest in every unit
+struct ZeroVector {
+    name: &'static str,
+    hash: &'static str,
+    bits: u32,
+    nibbles: u32,
+    bytes: u32,
+}
+
+const ZERO_VECTORS: [ZeroVector; 4] = [
+    ZeroVector {
+        name: "zeros 17 bits",
+        hash: "000059d1dbf35a6f26fe2d7ac82c968bf2e428c5428dd5de02f9b7d84fd5a488",
+        bits: 17,
+        nibbles: 4,
+        bytes: 2,
+    },
+    ZeroVector {
+        name: "zeros 4 bits",
+        hash: "0f00000000000000000000000000000000000000000000000000000000000000",
+        bits: 4,
+        nibbles: 1,
+        bytes: 0,
+    },
+    ZeroVector {
+        name: "zeros nenhum",
+        hash: "ff00000000000000000000000000000000000000000000000000000000000000",
+        bits: 0,
+        nibbles: 0,
+        bytes: 0,
+    },
+    ZeroVector {
+        name: "zeros todos",
+        hash: "0000000000000000000000000000000000000000000000000000000000000000",
+        bits: 256,
+        nibbles: 64,
+        bytes: 32,
+    },
+];
+
+/// Outcome of a self-test run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelfTestSummary {
+    pub passed: u32,
+    pub total: u32,
+}
+
+impl SelfTestSummary {
+    /// Checks if every case passed
+    pub fn all_passed(&self) -> bool {
+        self.passed == self.total
+    }
+}
+
+/// Runs every known-answer test, calling `report` with each case name and result
+pub fn run<F>(mut report: F) -> SelfTestSummary
+where
+    F: FnMut(&'static str, bool),
+{
+    let mut summary = SelfTestSummary { passed: 0, total: 0 };
+    let mut record = |name: &'static str, ok: bool| {
+        summary.total += 1;
+        if ok {
+            summary.passed += 1;
+        }
+        report(name, ok);
+    };
+
+    for vector in &DIGEST_VECTORS {
+        let hash = crate::mining::digest(vector.algorithm, vector.input);
+        record(vector.name, matches(&hash, vector.expected));
+    }
+
+    let preimage = kale_preimage();
+    let layout = hex::decode_array::<KALE_PREIMAGE_LEN>(KALE_PREIMAGE)
+        .is_ok_and(|expected| preimage.to_bytes(KALE_PREIMAGE_NONCE) == expected);
+    record("kale preimage", layout);
+    for vector in &KALE_VECTORS {
+        // Both the plain and the midstate path must give the expected digest
+        let ok = match vector.algorithm {
+            HashAlgorithm::Sha256 => check_kale(&Sha256Hasher, &preimage, vector),
+            HashAlgorithm::Keccak256 => check_kale(&Keccak256Hasher, &preimage, vector),
+        };
+        record(vector.name, ok);
+    }
+
+    for vector in &ZERO_VECTORS {
+        let ok = match hex::decode_array::<32>(vector.hash) {
+            Ok(hash) => {
+                ZeroChecker::count_zeros(&hash, ZeroUnit::Bits) == vector.bits
+                    && ZeroChecker::count_zeros(&hash, ZeroUnit::Nibbles) == vector.nibbles
+                    && ZeroChecker::count_zeros(&hash, ZeroUnit::Bytes) == vector.bytes
+            }
+            Err(_) => false,
+        };
+        record(vector.name, ok);
+    }
+
+    summary
+}
+
+/// Builds the preimage shared by the KALE vectors
+fn kale_preimage() -> KalePreimage {
+    let mut entropy = [0u8; 32];
+    for (i, byte) in entropy.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    let farmer = hex::decode_array::<32>(KALE_FARMER).unwrap_or([0u8; 32]);
+    KalePreimage::new(KALE_BLOCK_INDEX, entropy, farmer)
+}
+
+/// Checks one KALE vector through both hashing paths and the nibble counter
+fn check_kale<H: Hasher>(hasher: &H, preimage: &KalePreimage, vector: &KaleVector) -> bool {
+    let plain = hasher.hash(preimage, vector.nonce);
+    let prepared = hasher.hash_prepared(&hasher.prepare(preimage), vector.nonce);
+    matches(&plain, vector.expected)
+        && plain == prepared
+        && ZeroChecker::count_zeros(&plain, ZeroUnit::Nibbles) == vector.nibbles
+}
+
+/// Compares a digest with its expected hex
+fn matches(hash: &[u8; 32], expected: &str) -> bool {
+    hex::decode_array::<32>(expected).is_ok_and(|expected| *hash == expected)
+}
@@ -0,0 +1,120 @@
+//! Strkey module - Stellar account addresses (`G...`)
+//!
+//! A strkey is the base32 (RFC 4648, no padding) encoding of:
+//! version byte + 32-byte ed25519 public key + CRC16-XModem (little-endian).
+//! Decoding happens on the device so the host can send the address as-is.
+
+use core::fmt;
+
+use crate::crc::crc16_xmodem;
+
+/// Version byte of account ids (public keys) - encodes to a leading `G`
+pub const ACCOUNT_ID_VERSION: u8 = 6 << 3;
+
+/// Length of an account id strkey in characters
+pub const ACCOUNT_ID_LEN: usize = 56;
+
+/// Decoded length: version + key + checksum
+const DECODED_LEN: usize = 1 + 32 + 2;
+
+/// RFC 4648 base32 alphabet
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Strkey decoding errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StrkeyError {
+    /// Wrong number of characters
+    InvalidLength { expected: usize, found: usize },
+    /// Character outside the base32 alphabet, with its position
+    InvalidChar { position: usize, found: char },
+    /// Valid strkey of another kind (e.g. a secret seed `S...`)
+    InvalidVersion { found: u8 },
+    /// Checksum does not match - typo or corrupted address
+    ChecksumMismatch { expected: u16, found: u16 },
+}
+
+impl fmt::Display for StrkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrkeyError::InvalidLength { expected, found } => {
+                write!(f, "esperados {} caracteres, recebidos {}", expected, found)
+            }
+            StrkeyError::InvalidChar { position, found } => {
+                write!(f, "caractere '{}' inválido na posição {}", found, position)
+            }
+            StrkeyError::InvalidVersion { found } => {
+                write!(f, "versão 0x{:02x} inválida, esperado endereço G...", found)
+            }
+            StrkeyError::ChecksumMismatch { expected, found } => {
+                write!(f, "checksum inválido (esperado 0x{:04x}, recebido 0x{:04x})", expected, found)
+            }
+        }
+    }
+}
+
+/// Decodes a `G...` address into the raw ed25519 public key
+pub fn decode_account_id(s: &str) -> Result<[u8; 32], StrkeyError> {
+    if s.len() != ACCOUNT_ID_LEN {
+        return Err(StrkeyError::InvalidLength { expected: ACCOUNT_ID_LEN, found: s.len() });
+    }
+
+    // 56 characters * 5 bits = 280 bits = exactly 35 bytes
+    let mut decoded = [0u8; DECODED_LEN];
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    let mut out = 0;
+    for (position, &c) in s.as_bytes().iter().enumerate() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return Err(StrkeyError::InvalidChar { position, found: c as char }),
+        };
+        buffer = (buffer << 5) | value as u16;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded[out] = (buffer >> bits) as u8;
+            out += 1;
+        }
+    }
+
+    if decoded[0] != ACCOUNT_ID_VERSION {
+        return Err(StrkeyError::InvalidVersion { found: decoded[0] });
+    }
+
+    let expected = crc16_xmodem(&decoded[..33]);
+    let found = u16::from_le_bytes([decoded[33], decoded[34]]);
+    if expected != found {
+        return Err(StrkeyError::ChecksumMismatch { expected, found });
+    }
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&decoded[1..33]);
+    Ok(key)
+}
+
+/// Formats a raw public key as a `G...` address
+pub struct AccountId<'a>(pub &'a [u8; 32]);
+
+impl fmt::Display for AccountId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut raw = [0u8; DECODED_LEN];
+        raw[0] = ACCOUNT_ID_VERSION;
+        raw[1..33].copy_from_slice(self.0);
+        let checksum = crc16_xmodem(&raw[..33]);
+        raw[33..].copy_from_slice(&checksum.to_le_bytes());
+
+        let mut buffer = 0u16;
+        let mut bits = 0;
+        for &byte in &raw {
+            buffer = (buffer << 8) | byte as u16;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                let index = ((buffer >> bits) & 0x1f) as usize;
+                write!(f, "{}", ALPHABET[index] as char)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! Time module - the clock used to time jobs and benchmarks
+//!
+//! On the device this is the esp-hal system timer. Without the `esp`
+//! feature the library builds on the host (for the tests) and time stands
+//! still: every `Instant` is the same, so elapsed times and hashrates read 0.
+
+#[cfg(feature = "esp")]
+pub use esp_hal::time::{Duration, Instant};
+
+#[cfg(not(feature = "esp"))]
+pub use host::{Duration, Instant};
+
+#[cfg(not(feature = "esp"))]
+mod host {
+    use core::ops::{AddAssign, Sub};
+
+    /// Same API as `esp_hal::time::Duration`, in microseconds
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+    pub struct Duration(u64);
+
+    impl Duration {
+        pub const fn from_micros(micros: u64) -> Self {
+            Self(micros)
+        }
+
+        pub const fn from_millis(millis: u64) -> Self {
+            Self(millis.saturating_mul(1000))
+        }
+
+        pub const fn as_micros(&self) -> u64 {
+            self.0
+        }
+
+        pub const fn as_millis(&self) -> u64 {
+            self.0 / 1000
+        }
+    }
+
+    /// Same API as `esp_hal::time::Instant`, on a clock that never moves
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Instant(u64);
+
+    impl Instant {
+        pub const fn now() -> Self {
+            Self(0)
+        }
+
+        pub fn elapsed(&self) -> Duration {
+            Self::now() - *self
+        }
+    }
+
+    impl Sub for Instant {
+        type Output = Duration;
+
+        fn sub(self, earlier: Self) -> Duration {
+            Duration(self.0.saturating_sub(earlier.0))
+        }
+    }
+
+    impl AddAssign<Duration> for Instant {
+        fn add_assign(&mut self, duration: Duration) {
+            self.0 += duration.0;
+        }
+    }
+}
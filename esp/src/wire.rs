@@ -0,0 +1,202 @@
+//! Wire module - postcard-compatible payload encoding without allocation
+//!
+//! Follows the postcard wire format, so hosts can use the `postcard` crate
+//! (or any port of it) directly:
+//! - `u8` and `bool` as one byte, `[u8; N]` as N raw bytes
+//! - `u16`/`u32`/`u64`/`u128` as LEB128 varints
+//! - byte slices and strings as a varint length followed by the bytes
+//! - `Option` as a 0/1 tag, enums as a varint variant index
+
+use core::fmt;
+
+/// Payload encoding and decoding errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WireError {
+    /// Output buffer too small
+    BufferFull,
+    /// Payload ended in the middle of a value
+    UnexpectedEnd,
+    /// Varint longer than its type allows
+    InvalidVarint,
+    /// Tag byte out of range (bool, Option or enum)
+    InvalidTag(u32),
+    /// Bytes left after the last field
+    TrailingBytes,
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::BufferFull => write!(f, "payload grande demais"),
+            WireError::UnexpectedEnd => write!(f, "payload incompleto"),
+            WireError::InvalidVarint => write!(f, "varint inválido"),
+            WireError::InvalidTag(tag) => write!(f, "tag {} inválida", tag),
+            WireError::TrailingBytes => write!(f, "bytes sobrando no payload"),
+        }
+    }
+}
+
+/// Writes postcard values into a fixed buffer
+pub struct Encoder<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Encoder<'a> {
+    /// Creates an encoder writing from the start of `buf`
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// Bytes written so far
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Writes raw bytes (fixed-size arrays)
+    pub fn raw(&mut self, bytes: &[u8]) -> Result<(), WireError> {
+        let end = self.len + bytes.len();
+        if end > self.buf.len() {
+            return Err(WireError::BufferFull);
+        }
+        self.buf[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    pub fn u8(&mut self, value: u8) -> Result<(), WireError> {
+        self.raw(&[value])
+    }
+
+    pub fn bool(&mut self, value: bool) -> Result<(), WireError> {
+        self.u8(value as u8)
+    }
+
+    pub fn u32(&mut self, value: u32) -> Result<(), WireError> {
+        self.u128(value as u128)
+    }
+
+    pub fn u64(&mut self, value: u64) -> Result<(), WireError> {
+        self.u128(value as u128)
+    }
+
+    /// Writes a LEB128 varint - 7 bits per byte, high bit set on all but the last
+    pub fn u128(&mut self, mut value: u128) -> Result<(), WireError> {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                return self.u8(byte);
+            }
+            self.u8(byte | 0x80)?;
+        }
+    }
+
+    /// Writes a length-prefixed byte slice
+    pub fn bytes(&mut self, bytes: &[u8]) -> Result<(), WireError> {
+        self.u32(bytes.len() as u32)?;
+        self.raw(bytes)
+    }
+
+    /// Writes a length-prefixed string
+    pub fn str(&mut self, s: &str) -> Result<(), WireError> {
+        self.bytes(s.as_bytes())
+    }
+
+    /// Writes an `Option` tag; the caller writes the value after `true`
+    pub fn option(&mut self, is_some: bool) -> Result<(), WireError> {
+        self.bool(is_some)
+    }
+
+    /// Writes an enum variant index
+    pub fn variant(&mut self, index: u32) -> Result<(), WireError> {
+        self.u32(index)
+    }
+}
+
+/// Reads postcard values from a payload
+pub struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    /// Creates a decoder over a whole payload
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Reads `len` raw bytes
+    pub fn raw(&mut self, len: usize) -> Result<&'a [u8], WireError> {
+        if len > self.data.len() {
+            return Err(WireError::UnexpectedEnd);
+        }
+        let (value, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(value)
+    }
+
+    /// Reads a fixed-size byte array
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], WireError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.raw(N)?);
+        Ok(array)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.raw(1)?[0])
+    }
+
+    pub fn bool(&mut self) -> Result<bool, WireError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(WireError::InvalidTag(tag as u32)),
+        }
+    }
+
+    pub fn u32(&mut self) -> Result<u32, WireError> {
+        u32::try_from(self.varint(5)?).map_err(|_| WireError::InvalidVarint)
+    }
+
+    pub fn u64(&mut self) -> Result<u64, WireError> {
+        u64::try_from(self.varint(10)?).map_err(|_| WireError::InvalidVarint)
+    }
+
+    /// Reads a LEB128 varint of at most `max_len` bytes
+    fn varint(&mut self, max_len: usize) -> Result<u128, WireError> {
+        let mut value = 0u128;
+        for i in 0..max_len {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7f) as u128) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(WireError::InvalidVarint)
+    }
+
+    /// Reads a length-prefixed byte slice
+    pub fn bytes(&mut self) -> Result<&'a [u8], WireError> {
+        let len = self.u32()? as usize;
+        self.raw(len)
+    }
+
+    /// Reads an `Option` tag
+    pub fn option(&mut self) -> Result<bool, WireError> {
+        self.bool()
+    }
+
+    /// Reads an enum variant index
+    pub fn variant(&mut self) -> Result<u32, WireError> {
+        self.u32()
+    }
+
+    /// Checks that the whole payload was consumed
+    pub fn finish(self) -> Result<(), WireError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(WireError::TrailingBytes)
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Host tests of the device output - every `Event` rendered into a buffer
+//!
+//! Run without the board: `cargo +stable test --no-default-features --target <host triple>`
+
+use core::fmt;
+
+use kalesp::frame::{self, kind, Frame, FrameError, FrameReader, MAX_ENCODED};
+use kalesp::mining::{JobParams, MiningJob, MiningResult};
+use kalesp::time::Instant;
+use kalesp::msg::{
+    Command, Event, HashAlgorithm, MiningBudget, OutputMode, ParseError, Request, Sink, ZeroUnit, MAX_DIGEST_INPUT, MAX_LINE_LEN,
+};
+use kalesp::wire::{Decoder, Encoder};
+
+/// Collects everything an event writes, text or frames
+#[derive(Default)]
+struct Buffer(Vec<u8>);
+
+impl fmt::Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl Sink for Buffer {
+    fn write_bytes(&mut self, bytes: &[u8]) -> fmt::Result {
+        self.0.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+fn render(event: &Event, mode: OutputMode, id: Option<u32>) -> Vec<u8> {
+    let mut out = Buffer::default();
+    event.emit(&mut out, mode, id).unwrap();
+    out.0
+}
+
+fn render_text(event: &Event, mode: OutputMode, id: Option<u32>) -> String {
+    String::from_utf8(render(event, mode, id)).unwrap()
+}
+
+/// Splits a binary rendering back into its frame
+fn read_frame(bytes: &[u8]) -> Frame {
+    let mut reader = FrameReader::new();
+    let (last, body) = bytes.split_last().unwrap();
+    assert_eq!(*last, 0, "frame must end with its delimiter");
+    assert!(body.iter().all(|&byte| byte != 0));
+    for &byte in body {
+        assert!(reader.push(byte).is_none());
+    }
+    reader.push(0).unwrap().unwrap()
+}
+
+const RESULT: MiningResult = MiningResult {
+    block_index: 12345,
+    nonce: 11160,
+    hash: [0; 32],
+    zeros: 64,
+};
+
+#[test]
+fn text_reply() {
+    let event = Event::Zeros { zeros: 4, unit: ZeroUnit::Nibbles };
+    assert_eq!(
+        render_text(&event, OutputMode::Text, None),
+        "ZEROS: 4 nibbles configurados para mineração\r\n"
+    );
+}
+
+#[test]
+fn text_id_tags_every_line() {
+    let text = render_text(&Event::Unknown("xyz"), OutputMode::Text, Some(7));
+    assert_eq!(text.lines().count(), 2);
+    assert!(text.lines().all(|line| line.starts_with("#7 ")));
+}
+
+#[test]
+fn text_progress_starts_with_roadmap_fields() {
+    let job = MiningJob {
+        budget: None,
+        block_index: 12345,
+        zeros: 6,
+        zero_unit: ZeroUnit::Nibbles,
+        nonce_start: 0,
+        nonce_end: u64::MAX,
+        next_nonce: 11161,
+        tested: 11161,
+        best: Some(RESULT),
+        started: Instant::now(),
+        paused_at: None,
+    };
+    // Time stands still on the host, so the hashrate is 0 and there is no ETA
+    assert_eq!(
+        render_text(&Event::MineProgress(&job), OutputMode::Text, None),
+        "MINE_PROGRESS 11161 64 0 unit=nibbles block=12345 nonce=11161 eta=?\r\n"
+    );
+}
+
+#[test]
+fn text_result_starts_with_roadmap_fields() {
+    let event = Event::MineResult { result: &RESULT, unit: ZeroUnit::Nibbles, algorithm: HashAlgorithm::Keccak256 };
+    assert_eq!(
+        render_text(&event, OutputMode::Text, None),
+        format!("MINE_RESULT 11160 {} 64 unit=nibbles block=12345 algo=Keccak-256\r\n", "0".repeat(64))
+    );
+}
+
+#[test]
+fn text_unknown_echoes_a_full_line() {
+    let line = "x".repeat(MAX_LINE_LEN);
+    let text = render_text(&Event::Unknown(&line), OutputMode::Text, None);
+    assert!(text.starts_with(&format!("Comando recebido: '{line}'\r\n")));
+}
+
+#[test]
+fn text_parse_error_goes_to_its_command() {
+    let error = ParseError::OutOfRange("zeros");
+    let text = render_text(&Event::parse_error("zeros 99", &error), OutputMode::Text, None);
+    assert_eq!(text, "ZEROS_ERROR: 'zeros' fora do intervalo permitido\r\n");
+}
+
+#[test]
+fn json_result() {
+    let event = Event::MineResult { result: &RESULT, unit: ZeroUnit::Nibbles, algorithm: HashAlgorithm::Keccak256 };
+    let hash = "0".repeat(64);
+    assert_eq!(
+        render_text(&event, OutputMode::Json, Some(7)),
+        format!(
+            "{{\"type\":\"mine_result\",\"id\":7,\"block\":12345,\"nonce\":11160,\"hash\":\"{hash}\",\
+             \"zeros\":64,\"unit\":\"nibbles\",\"algo\":\"keccak256\"}}\r\n"
+        )
+    );
+}
+
+#[test]
+fn json_escapes_strings() {
+    let json = render_text(&Event::Unknown("a\"b\\c"), OutputMode::Json, None);
+    assert_eq!(json, "{\"type\":\"unknown_command\",\"command\":\"a\\\"b\\\\c\"}\r\n");
+}
+
+#[test]
+fn binary_reply_round_trip() {
+    let event = Event::Zeros { zeros: 4, unit: ZeroUnit::Nibbles };
+    let frame = read_frame(&render(&event, OutputMode::Binary, Some(5)));
+    assert_eq!(frame.kind, event.kind());
+    assert_eq!(frame.seq, 5);
+
+    let mut dec = Decoder::new(&frame.payload);
+    assert_eq!(dec.u8().unwrap(), 4);
+    assert_eq!(dec.variant().unwrap(), 1);
+    dec.finish().unwrap();
+}
+
+#[test]
+fn binary_result_round_trip() {
+    let event = Event::MineResult { result: &RESULT, unit: ZeroUnit::Nibbles, algorithm: HashAlgorithm::Keccak256 };
+    let frame = read_frame(&render(&event, OutputMode::Binary, None));
+    assert_eq!(frame.seq, 0);
+
+    let mut dec = Decoder::new(&frame.payload);
+    assert_eq!(dec.u32().unwrap(), RESULT.block_index);
+    assert_eq!(dec.u64().unwrap(), RESULT.nonce);
+    assert_eq!(dec.array::<32>().unwrap(), RESULT.hash);
+    assert_eq!(dec.u32().unwrap(), RESULT.zeros);
+    assert_eq!(dec.variant().unwrap(), 1);
+    assert_eq!(dec.variant().unwrap(), 1);
+    dec.finish().unwrap();
+}
+
+#[test]
+fn binary_nak_for_corrupt_frame() {
+    let event = Event::Zeros { zeros: 4, unit: ZeroUnit::Nibbles };
+    let mut bytes = render(&event, OutputMode::Binary, Some(5));
+    bytes[2] ^= 0x01;
+    let mut reader = FrameReader::new();
+    let error = bytes.iter().find_map(|&byte| reader.push(byte)).unwrap().unwrap_err();
+    assert!(matches!(error, FrameError::Crc { .. }));
+
+    let nak = read_frame(&render(&Event::Nak(error), OutputMode::Binary, None));
+    assert_eq!(nak.kind, kind::NAK);
+    assert_eq!(nak.payload.as_slice(), &[error.code()]);
+}
+
+#[test]
+fn binary_command_round_trip() {
+    let job = JobParams {
+        block_index: 12345,
+        entropy: [0x11; 32],
+        farmer: [0x22; 32],
+        zeros: 6,
+        batch_size: 256,
+    };
+    let mut payload = [0u8; frame::MAX_PAYLOAD];
+    let mut enc = Encoder::new(&mut payload);
+    enc.u32(job.block_index).unwrap();
+    enc.raw(&job.entropy).unwrap();
+    enc.raw(&job.farmer).unwrap();
+    enc.u8(job.zeros).unwrap();
+    enc.u32(job.batch_size).unwrap();
+    let payload = enc.written().to_vec();
+
+    let mut bytes = [0u8; MAX_ENCODED];
+    let len = frame::encode(kind::START_MINING, 9, &payload, &mut bytes).unwrap();
+    let frame = read_frame(&bytes[..len]);
+    assert_eq!(frame.seq, 9);
+    assert_eq!(Command::from_frame(&frame), Ok(Command::StartMining(job)));
+}
+
+#[test]
+fn longest_digest_line_fits() {
+    let line = format!("#4294967295 digest keccak256 0x{}", "ab".repeat(MAX_DIGEST_INPUT));
+    assert!(line.len() <= MAX_LINE_LEN);
+
+    let request = Request::from_line(&line).unwrap();
+    match Command::from_str(request.cmd) {
+        Ok(Command::Digest(HashAlgorithm::Keccak256, data)) => assert_eq!(data.len(), MAX_DIGEST_INPUT),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn start_mining_batch_is_capped() {
+    let entropy = "11".repeat(32);
+    let farmer = "22".repeat(32);
+    let line = |batch: u32| format!("start_mining 12345 {entropy} {farmer} 6 {batch}");
+    assert!(matches!(Command::from_str(&line(1024)), Ok(Command::StartMining(_))));
+    assert_eq!(Command::from_str(&line(1025)), Err(ParseError::OutOfRange("batch_size")));
+}
+
+#[test]
+fn start_mining_needs_a_farmer() {
+    let line = format!("start_mining 12345 {} {} 6 256", "11".repeat(32), "00".repeat(32));
+    assert_eq!(Command::from_str(&line), Err(ParseError::OutOfRange("farmer_hex")));
+}
+
+#[test]
+fn mine_budget_is_in_range() {
+    assert_eq!(Command::from_str("mine 100000"), Ok(Command::Mine(Some(MiningBudget::Nonces(100_000)))));
+    assert_eq!(Command::from_str("mine 5000ms"), Ok(Command::Mine(Some(MiningBudget::Millis(5000)))));
+    assert_eq!(Command::from_str("mine 0"), Err(ParseError::OutOfRange("budget")));
+    assert_eq!(Command::from_str("mine 0ms"), Err(ParseError::OutOfRange("budget")));
+    assert_eq!(Command::from_str("mine 18446744073709551616"), Err(ParseError::OutOfRange("budget")));
+    assert_eq!(Command::from_str("mine 4294967296ms"), Err(ParseError::OutOfRange("budget")));
+    assert_eq!(Command::from_str("mine 5s"), Err(ParseError::InvalidNumber("budget")));
+}
+
+#[test]
+fn bench_count_is_capped() {
+    assert!(matches!(Command::from_str("bench sha256 100000"), Ok(Command::Bench(..))));
+    assert_eq!(Command::from_str("bench sha256 4294967295"), Err(ParseError::OutOfRange("n")));
+}
@@ -0,0 +1,93 @@
+//! Host tests of the incremental miner - start, step, stop
+
+use kalesp::mining::{JobParams, MinerFactory, MiningError, MiningOutcome, MiningState, StepOutcome, ZeroChecker};
+
+fn job_state(zeros: u8) -> MiningState {
+    let mut state = MiningState::new();
+    state.load_job(&JobParams {
+        block_index: 12345,
+        entropy: [0x11; 32],
+        farmer: [0x22; 32],
+        zeros,
+        batch_size: 256,
+    });
+    state
+}
+
+#[test]
+fn solves_an_easy_job() {
+    let mut miner = MinerFactory::create_keccak256_miner();
+    miner.update_state(job_state(1));
+    miner.start(None).unwrap();
+
+    let result = loop {
+        match miner.step(256) {
+            StepOutcome::Finished(MiningOutcome::Solved(result)) => break result,
+            StepOutcome::Running => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    };
+    assert_eq!(result.block_index, 12345);
+    assert!(result.zeros >= 1);
+    assert_eq!(miner.verify(&job_state(1), result.nonce), result);
+    assert_eq!(ZeroChecker::count_zeros(&result.hash, JobParams::ZERO_UNIT), result.zeros);
+}
+
+#[test]
+fn stopped_job_reports_aborted() {
+    let mut miner = MinerFactory::create_sha256_miner();
+    assert_eq!(miner.stop().unwrap_err(), MiningError::NotRunning);
+
+    miner.update_state(job_state(64));
+    miner.start(None).unwrap();
+    miner.step(16);
+    let job = miner.stop().unwrap();
+    assert_eq!(job.tested, 16);
+    assert_eq!(job.next_nonce, 16);
+
+    assert_eq!(miner.resume(), Err(MiningError::Aborted));
+    assert_eq!(miner.pause(), Err(MiningError::Aborted));
+    assert_eq!(miner.stop().unwrap_err(), MiningError::Aborted);
+
+    // A new job clears it
+    miner.start(None).unwrap();
+    miner.stop().unwrap();
+    assert_eq!(miner.stop().unwrap_err(), MiningError::Aborted);
+}
+
+#[test]
+fn pass_is_small_before_the_hashrate_is_known() {
+    let mut miner = MinerFactory::create_keccak256_miner();
+    miner.update_state(job_state(64));
+    miner.start(None).unwrap();
+    // Time stands still on the host, so the job never has a hashrate
+    let job = miner.job().unwrap();
+    assert_eq!(job.slice(1024, 4), 16);
+    assert_eq!(job.slice(8, 4), 8);
+}
+
+#[test]
+fn new_job_returns_the_one_it_replaces() {
+    let mut miner = MinerFactory::create_keccak256_miner();
+    miner.update_state(job_state(64));
+    assert_eq!(miner.start(None).unwrap().map(|job| job.tested), None);
+    miner.step(8);
+    let replaced = miner.start(None).unwrap().unwrap();
+    assert_eq!(replaced.tested, 8);
+    assert_eq!(miner.job().unwrap().tested, 0);
+}
+
+#[test]
+fn mining_needs_a_farmer() {
+    let mut state = MiningState::new();
+    state.set_zeros(1);
+    state.set_entropy([0x11; 32]);
+    assert!(!state.is_ready_to_mine());
+
+    let mut miner = MinerFactory::create_sha256_miner();
+    miner.update_state(state);
+    assert_eq!(miner.start(None).unwrap_err(), MiningError::NotConfigured);
+
+    state.set_farmer([0x22; 32]);
+    assert!(state.is_ready_to_mine());
+}
@@ -0,0 +1,19 @@
+//! Host run of the device known-answer tests - the same cases `selftest` runs on the board
+
+use kalesp::selftest;
+
+#[test]
+fn every_case_passes() {
+    let mut failed = Vec::new();
+    let summary = selftest::run(|name, ok| {
+        if !ok {
+            failed.push(name);
+        }
+    });
+    assert!(summary.all_passed(), "failed: {:?}", failed);
+    assert!(summary.total > 0);
+}
+
+// Open item: the KALE vectors use a synthetic job (see `KALE_BLOCK_INDEX` in
+// `selftest.rs`); one must be replaced by a (block, entropy, farmer, nonce,
+// hash) tuple from a real `work()` call once one is recorded.
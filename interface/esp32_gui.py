@@ -5,12 +5,23 @@ Terminal/Assembler style
 """
 
 import flet as ft
+import json
 import threading
 import time
 import glob
 from python_serial import ESP32Serial
 
 
+def result_fields(message):
+    """Fields after MINE_RESULT/FOUND (nonce, hash, zeros, key=value...), or None for other lines"""
+    parts = message.split()
+    if parts and parts[0].startswith("#"):
+        parts = parts[1:]
+    if len(parts) > 1 and parts[0] in ("MINE_RESULT", "FOUND"):
+        return parts[1:]
+    return None
+
+
 class ESP32GUI:
     def __init__(self):
         self.esp32 = None
@@ -25,11 +36,12 @@ class ESP32GUI:
         # Device state
         self.device_info = {
             "device": "ESP32",
-            "firmware": "Serial Echo v1.0",
+            "firmware": "desconhecido",
             "uart": "115200 baud",
             "pins": "TX=GPIO1, RX=GPIO3",
             "zeros": 0,
             "entropy": 0,
+            "farmer": "",
             "last_nonce": -1,
         }
 
@@ -40,6 +52,7 @@ class ESP32GUI:
         self.log_column = None
         self.zeros_field = None
         self.entropy_field = None
+        self.farmer_field = None
         self.device_info_column = None
         self.mining_status = None
 
@@ -130,6 +143,7 @@ class ESP32GUI:
                 "MINING STATE:",
                 f"Zeros: {self.device_info['zeros']}",
                 f"Entropy: {self.device_info['entropy']}",
+                f"Farmer: {self.device_info['farmer'] or '-'}",
                 f"Last Nonce: {self.device_info['last_nonce']}",
             ]
 
@@ -140,7 +154,7 @@ class ESP32GUI:
                 color = ft.Colors.WHITE
                 if "MINING STATE:" in item:
                     color = ft.Colors.GREY_400
-                elif any(x in item for x in ["Zeros:", "Entropy:", "Last Nonce:"]):
+                elif any(x in item for x in ["Zeros:", "Entropy:", "Farmer:", "Last Nonce:"]):
                     color = ft.Colors.YELLOW_400
 
                 self.device_info_column.controls.append(
@@ -170,6 +184,10 @@ class ESP32GUI:
                 # Start custom monitoring
                 self.start_custom_monitoring()
 
+                # Typed JSON replies instead of scraping the text console
+                self.send_command("mode json")
+                self.send_command("hello")
+
                 self.log_message(f"Connected to ESP32 on {port}", "success")
                 self.log_message(
                     "Communication started! Commands: help, info, reset", "info"
@@ -220,6 +238,14 @@ class ESP32GUI:
         """Process ESP32 messages and update state"""
         self.log_message(message, "received")
 
+        if message.startswith("{"):
+            try:
+                event = json.loads(message)
+            except ValueError:
+                return
+            self.process_esp32_event(event)
+            return
+
         # Update state based on messages
         if message.startswith("ZEROS:"):
             try:
@@ -233,38 +259,115 @@ class ESP32GUI:
 
         elif message.startswith("ENTROPY:"):
             try:
-                entropy = int(message.split()[1])
+                entropy = message.split()[1]
                 self.device_info["entropy"] = entropy
-                self.entropy_field.value = str(entropy)
+                self.entropy_field.value = entropy
                 self.entropy_field.update()
                 self.update_device_info()
             except:
                 pass
 
+        elif message.startswith("FARMER:"):
+            try:
+                farmer = message.split()[1]
+                self.device_info["farmer"] = farmer
+                self.farmer_field.value = farmer
+                self.farmer_field.update()
+                self.update_device_info()
+            except:
+                pass
+
         elif "MINE_START:" in message:
             self.is_mining = True
             self.update_mining_status()
 
-        elif "MINE_RESULT:" in message or "FOUND:" in message:
+        elif result_fields(message) is not None:
+            self.is_mining = False
+            self.update_mining_status()
+            # MINE_RESULT/FOUND <nonce> <hash_hex> <zeros> key=value...
+            try:
+                nonce = int(result_fields(message)[0])
+                self.device_info["last_nonce"] = nonce
+                self.update_device_info()
+            except:
+                pass
+
+        elif "MINE_STOPPED:" in message or "MINE_EXHAUSTED:" in message:
             self.is_mining = False
             self.update_mining_status()
-            # Extract nonce if possible
-            if "encontrado:" in message:
-                try:
-                    nonce = int(message.split("encontrado:")[-1].strip())
-                    self.device_info["last_nonce"] = nonce
-                    self.update_device_info()
-                except:
-                    pass
 
         elif message.startswith("=== Reset Executado ==="):
-            self.device_info.update({"zeros": 0, "entropy": 0, "last_nonce": -1})
+            self.device_info.update({"zeros": 0, "entropy": 0, "farmer": "", "last_nonce": -1})
             self.zeros_field.value = "0"
             self.entropy_field.value = "0"
+            self.farmer_field.value = ""
             self.zeros_field.update()
             self.entropy_field.update()
+            self.farmer_field.update()
+            self.update_device_info()
+
+    def process_esp32_event(self, event):
+        """Process one JSON event (mode json) and update state"""
+        kind = event.get("type")
+
+        if kind == "zeros":
+            self.device_info["zeros"] = event["zeros"]
+            self.zeros_field.value = str(event["zeros"])
+            self.zeros_field.update()
+            self.update_device_info()
+
+        elif kind == "entropy":
+            self.device_info["entropy"] = event["entropy"]
+            self.entropy_field.value = event["entropy"]
+            self.entropy_field.update()
+            self.update_device_info()
+
+        elif kind == "farmer":
+            self.device_info["farmer"] = event["farmer"]
+            self.farmer_field.value = event["farmer"]
+            self.farmer_field.update()
             self.update_device_info()
 
+        elif kind == "mine_start":
+            self.is_mining = True
+            self.update_mining_status()
+
+        elif kind in ("found", "mine_result"):
+            self.is_mining = False
+            self.update_mining_status()
+            self.device_info["last_nonce"] = event["nonce"]
+            self.update_device_info()
+
+        elif kind in ("mine_stopped", "mine_exhausted"):
+            self.is_mining = False
+            self.update_mining_status()
+
+        elif kind == "reset":
+            self.device_info.update(
+                {
+                    "zeros": event["zeros"],
+                    "entropy": event["entropy"],
+                    "farmer": "",
+                    "last_nonce": -1,
+                }
+            )
+            self.zeros_field.value = str(event["zeros"])
+            self.entropy_field.value = event["entropy"]
+            self.farmer_field.value = ""
+            self.zeros_field.update()
+            self.entropy_field.update()
+            self.farmer_field.update()
+            self.update_device_info()
+
+        elif kind == "hello":
+            self.device_info["firmware"] = (
+                f"kalesp v{event['firmware']} (protocolo {event['protocol']})"
+            )
+            self.update_device_info()
+
+        elif kind == "error":
+            self.log_message(f"{event['source']}: {event['message']}", "error")
+
     def update_mining_status(self):
         """Updates mining status"""
         if self.mining_status:
@@ -298,9 +401,7 @@ class ESP32GUI:
 
     def on_mine_click(self, e):
         if self.is_mining:
-            self.is_mining = False
-            self.update_mining_status()
-            self.log_message("MINE_STOP: Mining interrupted by user", "sent")
+            self.send_command("stop")
         else:
             self.send_command("mine")
 
@@ -313,11 +414,20 @@ class ESP32GUI:
 
     def on_set_entropy_click(self, e):
         try:
-            entropy = int(self.entropy_field.value)
+            entropy = self.entropy_field.value.strip()
+            bytes.fromhex(entropy)
             self.send_command(f"entropy {entropy}")
         except ValueError:
             self.log_message("ERRO: Valor de entropy invalido", "error")
 
+    def on_set_farmer_click(self, e):
+        # G... address or 64 hex characters - the device validates either
+        farmer = self.farmer_field.value.strip()
+        if farmer:
+            self.send_command(f"farmer {farmer}")
+        else:
+            self.log_message("ERRO: Informe o endereco do farmer", "error")
+
     def clear_log(self, e):
         """Clear message log"""
         if self.log_column:
@@ -472,6 +582,16 @@ class ESP32GUI:
             text_style=ft.TextStyle(size=10, font_family="Courier New"),
         )
 
+        self.farmer_field = ft.TextField(
+            label="FARMER",
+            value="",
+            width=100,
+            bgcolor=ft.Colors.GREY_800,
+            color=ft.Colors.WHITE,
+            label_style=ft.TextStyle(size=10, font_family="Courier New"),
+            text_style=ft.TextStyle(size=10, font_family="Courier New"),
+        )
+
         self.mining_status = ft.Text(
             "IDLE", size=10, color=ft.Colors.GREY_500, font_family="Courier New"
         )
@@ -520,6 +640,23 @@ class ESP32GUI:
                         ],
                         spacing=5,
                     ),
+                    ft.Row(
+                        [
+                            self.farmer_field,
+                            ft.ElevatedButton(
+                                "SET F",
+                                on_click=self.on_set_farmer_click,
+                                bgcolor=ft.Colors.GREY_700,
+                                color=ft.Colors.WHITE,
+                                style=ft.ButtonStyle(
+                                    text_style=ft.TextStyle(
+                                        size=9, font_family="Courier New"
+                                    )
+                                ),
+                            ),
+                        ],
+                        spacing=5,
+                    ),
                     ft.Row(
                         [
                             ft.ElevatedButton(